@@ -4,16 +4,13 @@ use std::{
         atomic::{AtomicI32, Ordering},
         Arc,
     },
+    time::Duration,
 };
 
 use async_trait::async_trait;
-use mhw_toolkit::game_util;
-use once_cell::sync::Lazy;
 use tokio::sync::Mutex;
 
-use crate::configs;
-
-static CHAT_MESSAGE_SENDER: Lazy<game_util::ChatMessageSender> = Lazy::new(game_util::ChatMessageSender::new);
+use crate::{chat_queue::ChatQueue, configs};
 
 pub type ActionContext = Arc<Mutex<HashMap<String, String>>>;
 
@@ -27,6 +24,8 @@ pub struct SendChatMessageAction {
     msg: String,
     cnt: AtomicI32,
     enabled_cnt: bool,
+    priority: i32,
+    drop_after: Option<Duration>,
 }
 
 impl SendChatMessageAction {
@@ -35,8 +34,18 @@ impl SendChatMessageAction {
             msg: msg.to_string(),
             cnt: AtomicI32::new(1),
             enabled_cnt,
+            priority: 0,
+            drop_after: None,
         }
     }
+
+    pub fn set_priority(&mut self, priority: i32) {
+        self.priority = priority;
+    }
+
+    pub fn set_drop_after(&mut self, drop_after: Duration) {
+        self.drop_after = Some(drop_after);
+    }
 }
 
 #[async_trait]
@@ -50,7 +59,7 @@ impl AsAction for SendChatMessageAction {
         if self.enabled_cnt {
             msg = msg.replace("{{counter}}", &self.cnt.fetch_add(1, Ordering::SeqCst).to_string());
         }
-        CHAT_MESSAGE_SENDER.send(&msg);
+        ChatQueue::instance().push(msg, self.priority, self.drop_after).await;
     }
     async fn reset(&self) {
         if self.enabled_cnt {
@@ -61,6 +70,15 @@ impl AsAction for SendChatMessageAction {
 
 pub fn create_action(action_cfg: &configs::Action, enable_cnt: bool) -> Option<Box<dyn AsAction>> {
     match action_cfg.cmd {
-        configs::Command::SendChatMessage => Some(Box::new(SendChatMessageAction::new(&action_cfg.param, enable_cnt))),
+        configs::Command::SendChatMessage => {
+            let mut action = SendChatMessageAction::new(&action_cfg.param, enable_cnt);
+            if let Some(priority) = action_cfg.priority {
+                action.set_priority(priority);
+            }
+            if let Some(drop_after) = action_cfg.drop_after {
+                action.set_drop_after(Duration::from_secs_f32(drop_after));
+            }
+            Some(Box::new(action))
+        }
     }
 }
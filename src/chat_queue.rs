@@ -0,0 +1,244 @@
+use std::{
+    cmp::Ordering,
+    collections::BinaryHeap,
+    sync::{
+        atomic::{self, AtomicU64},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
+use log::debug;
+use mhw_toolkit::game_util;
+use once_cell::sync::Lazy;
+use tokio::sync::{Mutex, Notify};
+
+use crate::configs::ChatQueueConfig;
+
+static CHAT_MESSAGE_SENDER: Lazy<game_util::ChatMessageSender> = Lazy::new(game_util::ChatMessageSender::new);
+static CHAT_QUEUE: Lazy<Arc<ChatQueue>> = Lazy::new(|| Arc::new(ChatQueue::new()));
+
+/// 聊天消息发送队列
+///
+/// 所有聊天消息行为统一由此发送，按优先级排序并限制发送频率
+pub struct ChatQueue {
+    queue: Mutex<BinaryHeap<QueuedMessage>>,
+    limiter: Mutex<RateLimiter>,
+    notify: Notify,
+    seq: AtomicU64,
+}
+
+impl ChatQueue {
+    fn new() -> Self {
+        let default_cfg = ChatQueueConfig::default();
+        ChatQueue {
+            queue: Mutex::new(BinaryHeap::new()),
+            limiter: Mutex::new(RateLimiter::new(
+                Duration::from_secs_f32(default_cfg.min_interval),
+                default_cfg.max_burst,
+            )),
+            notify: Notify::new(),
+            seq: AtomicU64::new(0),
+        }
+    }
+
+    /// 获取发送队列实例
+    pub fn instance() -> Arc<ChatQueue> {
+        CHAT_QUEUE.clone()
+    }
+
+    /// 应用配置
+    pub async fn configure(&self, cfg: &ChatQueueConfig) {
+        *self.limiter.lock().await = RateLimiter::new(Duration::from_secs_f32(cfg.min_interval), cfg.max_burst);
+    }
+
+    /// 加入发送队列
+    ///
+    /// `priority` 越大越优先发送，`drop_after` 为消息在队列中的最长等待时间
+    pub async fn push(&self, msg: String, priority: i32, drop_after: Option<Duration>) {
+        let message = QueuedMessage {
+            msg,
+            priority,
+            seq: self.seq.fetch_add(1, atomic::Ordering::SeqCst),
+            enqueue_time: Instant::now(),
+            drop_after,
+        };
+        self.queue.lock().await.push(message);
+        self.notify.notify_one();
+    }
+
+    /// 发送循环
+    pub async fn run(&self) {
+        loop {
+            // 等待消息
+            while self.queue.lock().await.is_empty() {
+                self.notify.notified().await;
+            }
+            // 等待发送配额
+            let delay = self.limiter.lock().await.delay(Instant::now());
+            if !delay.is_zero() {
+                tokio::time::sleep(delay).await;
+                continue;
+            }
+            // 取出优先级最高且未过期的消息
+            let message = {
+                let now = Instant::now();
+                let mut queue = self.queue.lock().await;
+                pop_valid(&mut queue, now)
+            };
+            if let Some(message) = message {
+                self.limiter.lock().await.consume(Instant::now());
+                CHAT_MESSAGE_SENDER.send(&message.msg);
+            }
+        }
+    }
+}
+
+fn pop_valid(queue: &mut BinaryHeap<QueuedMessage>, now: Instant) -> Option<QueuedMessage> {
+    while let Some(message) = queue.pop() {
+        if message.is_expired(now) {
+            debug!("消息等待超时，已丢弃：{}", message.msg);
+            continue;
+        }
+        return Some(message);
+    }
+    None
+}
+
+struct QueuedMessage {
+    msg: String,
+    priority: i32,
+    seq: u64,
+    enqueue_time: Instant,
+    drop_after: Option<Duration>,
+}
+
+impl QueuedMessage {
+    fn is_expired(&self, now: Instant) -> bool {
+        self.drop_after
+            .is_some_and(|drop_after| self.enqueue_time.checked_add(drop_after).is_some_and(|deadline| deadline < now))
+    }
+}
+
+impl PartialEq for QueuedMessage {
+    fn eq(&self, other: &Self) -> bool {
+        self.priority == other.priority && self.seq == other.seq
+    }
+}
+
+impl Eq for QueuedMessage {}
+
+impl PartialOrd for QueuedMessage {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for QueuedMessage {
+    /// 优先级高者优先，同优先级先入队者优先
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.priority.cmp(&other.priority).then_with(|| other.seq.cmp(&self.seq))
+    }
+}
+
+/// 令牌桶限流器
+///
+/// 最多连续发送 `max_burst` 条消息（不受 `min_interval` 限制），之后每 `min_interval` 恢复一条
+struct RateLimiter {
+    min_interval: Duration,
+    max_burst: u32,
+    tokens: f64,
+    last_refill: Option<Instant>,
+}
+
+impl RateLimiter {
+    fn new(min_interval: Duration, max_burst: u32) -> Self {
+        let max_burst = max_burst.max(1);
+        RateLimiter {
+            min_interval,
+            max_burst,
+            tokens: max_burst as f64,
+            last_refill: None,
+        }
+    }
+
+    fn refill(&mut self, now: Instant) {
+        if let Some(last_refill) = self.last_refill {
+            let elapsed = now.saturating_duration_since(last_refill);
+            let refilled = if self.min_interval.is_zero() {
+                self.max_burst as f64
+            } else {
+                elapsed.as_secs_f64() / self.min_interval.as_secs_f64()
+            };
+            self.tokens = (self.tokens + refilled).min(self.max_burst as f64);
+        }
+        self.last_refill = Some(now);
+    }
+
+    /// 距离下一次可发送的等待时间
+    fn delay(&mut self, now: Instant) -> Duration {
+        self.refill(now);
+        if self.tokens >= 1.0 {
+            Duration::ZERO
+        } else {
+            self.min_interval.mul_f64(1.0 - self.tokens)
+        }
+    }
+
+    /// 消耗一次发送配额
+    fn consume(&mut self, now: Instant) {
+        self.refill(now);
+        self.tokens = (self.tokens - 1.0).max(0.0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(msg: &str, priority: i32, seq: u64, drop_after: Option<Duration>) -> QueuedMessage {
+        QueuedMessage {
+            msg: msg.to_string(),
+            priority,
+            seq,
+            enqueue_time: Instant::now(),
+            drop_after,
+        }
+    }
+
+    #[test]
+    fn test_queue_order() {
+        let mut queue = BinaryHeap::new();
+        queue.push(message("a", 0, 0, None));
+        queue.push(message("b", 1, 1, None));
+        queue.push(message("c", 0, 2, None));
+        let now = Instant::now();
+        let order: Vec<String> = std::iter::from_fn(|| pop_valid(&mut queue, now)).map(|m| m.msg).collect();
+        assert_eq!(order, vec!["b", "a", "c"]);
+    }
+
+    #[test]
+    fn test_queue_drop_expired() {
+        let mut queue = BinaryHeap::new();
+        queue.push(message("a", 1, 0, Some(Duration::from_secs(3))));
+        queue.push(message("b", 0, 1, None));
+        let later = Instant::now() + Duration::from_secs(5);
+        assert_eq!(pop_valid(&mut queue, later).unwrap().msg, "b");
+        assert!(pop_valid(&mut queue, later).is_none());
+    }
+
+    #[test]
+    fn test_rate_limiter_burst() {
+        let start = Instant::now();
+        let mut limiter = RateLimiter::new(Duration::from_secs(1), 2);
+        for _ in 0..2 {
+            assert!(limiter.delay(start).is_zero());
+            limiter.consume(start);
+        }
+        assert_eq!(limiter.delay(start), Duration::from_secs(1));
+        let later = start + Duration::from_secs(1);
+        assert!(limiter.delay(later).is_zero());
+        limiter.consume(later);
+        assert!(!limiter.delay(later).is_zero());
+    }
+}
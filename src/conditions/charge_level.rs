@@ -0,0 +1,93 @@
+use async_trait::async_trait;
+use log::error;
+
+use crate::{
+    actions::ActionContext,
+    configs::{CheckCondition, TriggerCondition},
+    event::{Event, EventType},
+    game_context::is_charge_weapon,
+    triggers::{AsCheckCondition, AsTriggerCondition, SharedContext},
+};
+
+use super::{CheckFn, TriggerFn};
+
+/// 大剑和大锤蓄力等级条件
+pub struct ChargeLevelCondition {
+    trigger_fn: TriggerFn,
+    check_fn: CheckFn,
+    shared_ctx: SharedContext,
+}
+
+impl ChargeLevelCondition {
+    pub fn new_trigger(cond: &TriggerCondition, shared_ctx: SharedContext) -> Self {
+        let cond = cond.clone();
+        let trigger_fn: TriggerFn = if let TriggerCondition::ChargeLevel { new, old, released } = cond {
+            Box::new(move |event| {
+                if let Event::ChargeLevelChanged {
+                    new: new_event,
+                    old: old_event,
+                } = event
+                {
+                    let is_released = *new_event == 0 && *old_event > 0;
+                    released.map_or(true, |released| released == is_released)
+                        && new.as_ref().map_or(true, |new| new == new_event)
+                        && old.as_ref().map_or(true, |old| old == old_event)
+                } else {
+                    false
+                }
+            })
+        } else {
+            error!("internal: ChargeLevelCondition cmp_fn 参数不正确");
+            Box::new(|_| false)
+        };
+
+        ChargeLevelCondition {
+            trigger_fn,
+            check_fn: Box::new(|_| false),
+            shared_ctx,
+        }
+    }
+
+    pub fn new_check(cond: &CheckCondition, shared_ctx: SharedContext) -> Self {
+        let cond = cond.clone();
+        let check_fn: CheckFn = if let CheckCondition::ChargeLevel { value } = cond {
+            Box::new(move |ctx| is_charge_weapon(&ctx.weapon_type) && value == ctx.charge_level)
+        } else {
+            error!("internal: ChargeLevelCondition cmp_fn 参数不正确");
+            Box::new(|_| false)
+        };
+
+        ChargeLevelCondition {
+            trigger_fn: Box::new(|_| false),
+            check_fn,
+            shared_ctx,
+        }
+    }
+}
+
+#[async_trait]
+impl AsTriggerCondition for ChargeLevelCondition {
+    async fn check(&self, event: &Event, action_ctx: &ActionContext) -> bool {
+        if !(self.trigger_fn)(event) {
+            return false;
+        }
+        if let Event::ChargeLevelChanged { new, old } = event {
+            // 蓄力结束时使用释放前的等级
+            let level = if *new == 0 { old } else { new };
+            let mut action_ctx = action_ctx.lock().await;
+            action_ctx.insert("charge_level".to_string(), level.to_string());
+        }
+        true
+    }
+
+    fn event_type(&self) -> EventType {
+        EventType::ChargeLevelChanged
+    }
+}
+
+#[async_trait]
+impl AsCheckCondition for ChargeLevelCondition {
+    async fn check(&self, _action_ctx: &ActionContext) -> bool {
+        (self.check_fn)(&*self.shared_ctx.read().await)
+    }
+}
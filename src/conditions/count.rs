@@ -0,0 +1,126 @@
+use std::collections::VecDeque;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use log::error;
+use tokio::sync::Mutex;
+
+use crate::{
+    actions::ActionContext,
+    configs::{CheckCondition, ValueCmp},
+    triggers::AsCheckCondition,
+};
+
+/// 计数条件
+///
+/// 统计触发器自身触发条件在时间窗口内（或本次任务内）被满足的次数
+pub struct CountCondition {
+    cond_value: ValueCmp,
+    counter: Mutex<WindowCounter>,
+}
+
+impl CountCondition {
+    pub fn new_check(cond: &CheckCondition) -> Self {
+        let cond = cond.clone();
+        if let CheckCondition::Count { value, window_ms } = cond {
+            // window_ms 已在加载配置时检查
+            let window = window_ms.and_then(Duration::try_milliseconds);
+            CountCondition {
+                cond_value: value,
+                counter: Mutex::new(WindowCounter::new(window)),
+            }
+        } else {
+            error!("internal: CountCondition cond 参数不正确");
+            panic!("internal: CountCondition cond 参数不正确");
+        }
+    }
+}
+
+#[async_trait]
+impl AsCheckCondition for CountCondition {
+    async fn check(&self, action_ctx: &ActionContext) -> bool {
+        let count = self.counter.lock().await.count(Utc::now());
+        action_ctx.lock().await.insert("count".to_string(), count.to_string());
+        self.cond_value == count
+    }
+
+    async fn on_triggered(&self) {
+        self.counter.lock().await.hit(Utc::now());
+    }
+
+    async fn reset(&self) {
+        self.counter.lock().await.clear();
+    }
+}
+
+/// 时间窗口计数器
+///
+/// 窗口为 `None` 时不过期，仅在重置时清空
+struct WindowCounter {
+    window: Option<Duration>,
+    records: VecDeque<DateTime<Utc>>,
+}
+
+impl WindowCounter {
+    fn new(window: Option<Duration>) -> Self {
+        WindowCounter {
+            window,
+            records: VecDeque::new(),
+        }
+    }
+
+    fn hit(&mut self, now: DateTime<Utc>) {
+        self.records.push_back(now);
+        self.clear_expired(now);
+    }
+
+    fn count(&mut self, now: DateTime<Utc>) -> i32 {
+        self.clear_expired(now);
+        self.records.len() as i32
+    }
+
+    fn clear(&mut self) {
+        self.records.clear();
+    }
+
+    fn clear_expired(&mut self, now: DateTime<Utc>) {
+        let Some(window) = self.window else {
+            return;
+        };
+        while let Some(front) = self.records.front() {
+            // 超出时间范围时视为未过期
+            if front.checked_add_signed(window).is_some_and(|end| end < now) {
+                self.records.pop_front();
+            } else {
+                break;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_window_counter_expire() {
+        let start = Utc::now();
+        let mut counter = WindowCounter::new(Duration::try_milliseconds(1000));
+        counter.hit(start);
+        counter.hit(start + Duration::try_milliseconds(500).unwrap());
+        assert_eq!(counter.count(start + Duration::try_milliseconds(900).unwrap()), 2);
+        assert_eq!(counter.count(start + Duration::try_milliseconds(1200).unwrap()), 1);
+        assert_eq!(counter.count(start + Duration::try_milliseconds(2000).unwrap()), 0);
+    }
+
+    #[test]
+    fn test_window_counter_unlimited() {
+        let start = Utc::now();
+        let mut counter = WindowCounter::new(None);
+        counter.hit(start);
+        counter.hit(start + Duration::try_hours(1).unwrap());
+        assert_eq!(counter.count(start + Duration::try_hours(2).unwrap()), 2);
+        counter.clear();
+        assert_eq!(counter.count(start), 0);
+    }
+}
@@ -1,20 +1,23 @@
 use std::time::Duration;
 
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use log::error;
 
 use crate::{
     actions::ActionContext,
-    configs::{CheckCondition, FsmConfig, ValueCmp},
-    game::DamageCollector,
-    triggers::{AsCheckCondition, SharedContext},
+    configs::{CheckCondition, FsmList, TriggerCondition, ValueCmp},
+    event::{Event, EventType},
+    game::{DamageCollector, DamageFilter, DamageStats},
+    triggers::{AsCheckCondition, AsTriggerCondition, SharedContext, SingleCoolDown, TRIGGER_TIME_KEY},
 };
 
 pub struct DamageCondition {
-    cond_damage: ValueCmp,
-    cond_fsm: FsmConfig,
+    cond_stats: DamageStatsCmp,
+    cond_fsm: Option<FsmList>,
     cond_timeout: i32,
     cond_break_on_fsm_changed: bool,
+    cond_filter: DamageFilter,
     shared_ctx: SharedContext,
 }
 
@@ -23,17 +26,37 @@ impl DamageCondition {
         let cond = cond.clone();
         if let CheckCondition::Damage {
             damage,
+            hits,
+            crits,
+            max_hit,
+            min_hit,
+            avg_hit,
             fsm,
             timeout,
             break_on_fsm_changed,
+            crit,
+            tenderized,
+            same_monster,
         } = cond
         {
             let timeout = timeout.unwrap_or(2000);
             DamageCondition {
-                cond_damage: damage,
+                cond_stats: DamageStatsCmp {
+                    damage,
+                    hits,
+                    crits,
+                    max_hit,
+                    min_hit,
+                    avg_hit,
+                },
                 cond_fsm: fsm,
                 cond_timeout: timeout,
                 cond_break_on_fsm_changed: break_on_fsm_changed,
+                cond_filter: DamageFilter {
+                    crit,
+                    tenderized,
+                    same_monster,
+                },
                 shared_ctx,
             }
         } else {
@@ -48,16 +71,171 @@ impl AsCheckCondition for DamageCondition {
     async fn check(&self, action_ctx: &ActionContext) -> bool {
         let damage_collector = DamageCollector::instance();
         let now_fsm = self.shared_ctx.read().await.fsm;
-        if self.cond_fsm == now_fsm {
-            let damage = if self.cond_break_on_fsm_changed {
-                damage_collector.collect_fsm(&now_fsm, Duration::from_millis(self.cond_timeout as u64)).await
-            } else {
-                damage_collector.collect_time(Duration::from_millis(self.cond_timeout as u64)).await
-            };
-            action_ctx.lock().await.insert("damage".to_string(), damage.to_string());
-            self.cond_damage == damage
+        if let Some(cond_fsm) = &self.cond_fsm {
+            if !cond_fsm.contains(&now_fsm) {
+                return false;
+            }
+        }
+        // 以触发时间作为收集起点
+        let start_time = trigger_time(action_ctx).await;
+        let timeout = Duration::from_millis(self.cond_timeout as u64);
+        let stats = if self.cond_break_on_fsm_changed {
+            match &self.cond_fsm {
+                Some(cond_fsm) => {
+                    damage_collector
+                        .collect_fsm(|fsm| cond_fsm.contains(fsm), start_time, timeout, &self.cond_filter)
+                        .await
+                }
+                None => {
+                    damage_collector.collect_fsm(|fsm| *fsm == now_fsm, start_time, timeout, &self.cond_filter).await
+                }
+            }
+        } else {
+            damage_collector.collect_time(start_time, timeout, &self.cond_filter).await
+        };
+        insert_stats(action_ctx, &stats).await;
+        let move_name = damage_collector.move_name(&now_fsm).await;
+        action_ctx.lock().await.insert("move".to_string(), move_name);
+        if let Some(target) = stats.first_target {
+            insert_monster_damage(action_ctx, target).await;
+        }
+        self.cond_stats.matches(&stats)
+    }
+}
+
+/// 单次命中伤害触发条件
+pub struct DamageTriggerCondition {
+    cond_value: Option<ValueCmp>,
+    cond_filter: DamageFilter,
+    cooldown: Option<SingleCoolDown>,
+}
+
+impl DamageTriggerCondition {
+    pub fn new_trigger(cond: &TriggerCondition) -> Self {
+        let cond = cond.clone();
+        if let TriggerCondition::Damage {
+            value,
+            crit,
+            tenderized,
+            cooldown,
+        } = cond
+        {
+            DamageTriggerCondition {
+                cond_value: value,
+                cond_filter: DamageFilter {
+                    crit,
+                    tenderized,
+                    ..Default::default()
+                },
+                cooldown: cooldown.map(SingleCoolDown::new),
+            }
         } else {
-            false
+            error!("internal: DamageTriggerCondition cond 参数不正确");
+            panic!("internal: DamageTriggerCondition cond 参数不正确");
         }
     }
 }
+
+#[async_trait]
+impl AsTriggerCondition for DamageTriggerCondition {
+    async fn check(&self, event: &Event, action_ctx: &ActionContext) -> bool {
+        let Event::Damage {
+            damage,
+            is_crit,
+            is_tenderized,
+            attack_id,
+            target,
+        } = event
+        else {
+            return false;
+        };
+        if !self.cond_filter.matches_hit(*is_crit, *is_tenderized) {
+            return false;
+        }
+        if let Some(value) = &self.cond_value {
+            if value != damage {
+                return false;
+            }
+        }
+        // 命中冷却在触发器实际触发后才开始计时
+        if let Some(cd) = &self.cooldown {
+            if !cd.is_ready().await {
+                return false;
+            }
+        }
+        {
+            let mut action_ctx = action_ctx.lock().await;
+            action_ctx.insert("damage".to_string(), damage.to_string());
+            action_ctx.insert("attack_id".to_string(), attack_id.to_string());
+        }
+        insert_monster_damage(action_ctx, *target).await;
+        true
+    }
+
+    fn event_type(&self) -> EventType {
+        EventType::Damage
+    }
+
+    async fn on_fired(&self) {
+        if let Some(cd) = &self.cooldown {
+            cd.check_set().await;
+        }
+    }
+}
+
+/// 伤害统计值比较条件
+///
+/// 为 `None` 的条件不参与比较
+struct DamageStatsCmp {
+    damage: Option<ValueCmp>,
+    hits: Option<ValueCmp>,
+    crits: Option<ValueCmp>,
+    max_hit: Option<ValueCmp>,
+    min_hit: Option<ValueCmp>,
+    avg_hit: Option<ValueCmp>,
+}
+
+impl DamageStatsCmp {
+    fn matches(&self, stats: &DamageStats) -> bool {
+        let cmp = |cond: &Option<ValueCmp>, value: i32| cond.as_ref().map_or(true, |c| *c == value);
+        cmp(&self.damage, stats.damage)
+            && cmp(&self.hits, stats.hits)
+            && cmp(&self.crits, stats.crits)
+            && cmp(&self.max_hit, stats.max_hit)
+            && cmp(&self.min_hit, stats.min_hit)
+            && cmp(&self.avg_hit, stats.avg_hit())
+    }
+}
+
+/// 获取触发时间，不存在时使用当前时间
+async fn trigger_time(action_ctx: &ActionContext) -> DateTime<Utc> {
+    action_ctx
+        .lock()
+        .await
+        .get(TRIGGER_TIME_KEY)
+        .and_then(|v| v.parse::<i64>().ok())
+        .and_then(DateTime::from_timestamp_millis)
+        .unwrap_or_else(Utc::now)
+}
+
+/// 写入怪物编号和该怪物在本次任务中的累计伤害
+async fn insert_monster_damage(action_ctx: &ActionContext, target: usize) {
+    if let Some((monster, monster_damage)) = DamageCollector::instance().monster_damage(target).await {
+        let mut action_ctx = action_ctx.lock().await;
+        action_ctx.insert("monster".to_string(), monster.to_string());
+        action_ctx.insert("monster_damage".to_string(), monster_damage.to_string());
+    }
+}
+
+/// 写入伤害统计占位符
+async fn insert_stats(action_ctx: &ActionContext, stats: &DamageStats) {
+    let hit_list = stats.hit_list.iter().map(|v| v.to_string()).collect::<Vec<_>>().join("+");
+    let mut action_ctx = action_ctx.lock().await;
+    action_ctx.insert("damage".to_string(), stats.damage.to_string());
+    action_ctx.insert("hits".to_string(), stats.hits.to_string());
+    action_ctx.insert("crits".to_string(), stats.crits.to_string());
+    action_ctx.insert("max_hit".to_string(), stats.max_hit.to_string());
+    action_ctx.insert("min_hit".to_string(), stats.min_hit.to_string());
+    action_ctx.insert("avg_hit".to_string(), stats.avg_hit().to_string());
+    action_ctx.insert("hit_list".to_string(), hit_list);
+}
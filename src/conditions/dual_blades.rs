@@ -0,0 +1,111 @@
+use async_trait::async_trait;
+use log::error;
+
+use crate::{
+    actions::ActionContext,
+    configs::{CheckCondition, NewOldValueCmp, TriggerCondition},
+    event::{Event, EventType},
+    game_context::DualBlades,
+    triggers::{AsCheckCondition, AsTriggerCondition, SharedContext},
+};
+
+use super::{compare_new_old, compare_state_change};
+
+/// 双刀条件
+pub struct DualBladesCondition {
+    shared_ctx: SharedContext,
+    cond_demon_mode: Option<bool>,
+    cond_archdemon_gauge: Option<NewOldValueCmp>,
+}
+
+impl DualBladesCondition {
+    pub fn new_trigger(cond: &TriggerCondition, shared_ctx: SharedContext) -> Self {
+        let cond = cond.clone();
+        if let TriggerCondition::DualBlades {
+            demon_mode,
+            archdemon_gauge,
+        } = cond
+        {
+            DualBladesCondition {
+                shared_ctx,
+                cond_demon_mode: demon_mode,
+                cond_archdemon_gauge: *archdemon_gauge,
+            }
+        } else {
+            error!("internal: DualBladesCondition cond 参数不正确");
+            panic!("internal: DualBladesCondition cond 参数不正确");
+        }
+    }
+
+    pub fn new_check(cond: &CheckCondition, shared_ctx: SharedContext) -> Self {
+        let cond = cond.clone();
+        if let CheckCondition::DualBlades {
+            demon_mode,
+            archdemon_gauge,
+        } = cond
+        {
+            DualBladesCondition {
+                shared_ctx,
+                cond_demon_mode: demon_mode,
+                // 检查条件只比较当前值
+                cond_archdemon_gauge: archdemon_gauge.map(|archdemon_gauge| NewOldValueCmp {
+                    new: Some(archdemon_gauge),
+                    old: None,
+                }),
+            }
+        } else {
+            error!("internal: DualBladesCondition cond 参数不正确");
+            panic!("internal: DualBladesCondition cond 参数不正确");
+        }
+    }
+
+    fn matches_change(&self, new: &DualBlades, old: &DualBlades) -> bool {
+        compare_state_change(self.cond_demon_mode, new.demon_mode, old.demon_mode)
+            && compare_new_old(&self.cond_archdemon_gauge, new.archdemon_gauge as i32, old.archdemon_gauge as i32)
+    }
+
+    fn matches_state(&self, dual_blades: &DualBlades) -> bool {
+        self.cond_demon_mode.map_or(true, |demon_mode| demon_mode == dual_blades.demon_mode)
+            && compare_new_old(&self.cond_archdemon_gauge, dual_blades.archdemon_gauge as i32, 0)
+    }
+}
+
+async fn insert_dual_blades(action_ctx: &ActionContext, dual_blades: &DualBlades) {
+    let mut action_ctx = action_ctx.lock().await;
+    action_ctx.insert("archdemon_gauge".to_string(), (dual_blades.archdemon_gauge as i32).to_string());
+    action_ctx.insert("stamina_drain".to_string(), format!("{:.1}", dual_blades.stamina_drain));
+}
+
+#[async_trait]
+impl AsTriggerCondition for DualBladesCondition {
+    async fn check(&self, event: &Event, action_ctx: &ActionContext) -> bool {
+        if !matches!(event, Event::DualBlades) {
+            return false;
+        }
+        let ctx = self.shared_ctx.read().await;
+        let Some(last_ctx) = ctx.last_ctx.as_ref() else {
+            return false;
+        };
+        if !self.matches_change(&ctx.dual_blades, &last_ctx.dual_blades) {
+            return false;
+        }
+        insert_dual_blades(action_ctx, &ctx.dual_blades).await;
+        true
+    }
+
+    fn event_type(&self) -> EventType {
+        EventType::DualBlades
+    }
+}
+
+#[async_trait]
+impl AsCheckCondition for DualBladesCondition {
+    async fn check(&self, action_ctx: &ActionContext) -> bool {
+        let ctx = self.shared_ctx.read().await;
+        if ctx.weapon_type != 2 || !self.matches_state(&ctx.dual_blades) {
+            return false;
+        }
+        insert_dual_blades(action_ctx, &ctx.dual_blades).await;
+        true
+    }
+}
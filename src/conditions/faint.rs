@@ -0,0 +1,74 @@
+use async_trait::async_trait;
+use log::error;
+
+use crate::{
+    actions::ActionContext,
+    configs::{CheckCondition, TriggerCondition, ValueCmp},
+    event::{Event, EventType},
+    triggers::{AsCheckCondition, AsTriggerCondition, SharedContext},
+};
+
+/// 猫车条件
+pub struct FaintCondition {
+    cond_faint_count: Option<ValueCmp>,
+    shared_ctx: SharedContext,
+}
+
+impl FaintCondition {
+    pub fn new_trigger(cond: &TriggerCondition, shared_ctx: SharedContext) -> Self {
+        let cond = cond.clone();
+        if let TriggerCondition::PlayerFainted { faint_count } = cond {
+            FaintCondition {
+                cond_faint_count: faint_count,
+                shared_ctx,
+            }
+        } else {
+            error!("internal: FaintCondition cond 参数不正确");
+            panic!("internal: FaintCondition cond 参数不正确");
+        }
+    }
+
+    pub fn new_check(cond: &CheckCondition, shared_ctx: SharedContext) -> Self {
+        let cond = cond.clone();
+        if let CheckCondition::Faints { value } = cond {
+            FaintCondition {
+                cond_faint_count: Some(value),
+                shared_ctx,
+            }
+        } else {
+            error!("internal: FaintCondition cond 参数不正确");
+            panic!("internal: FaintCondition cond 参数不正确");
+        }
+    }
+
+    fn matches(&self, faint_count: i32) -> bool {
+        self.cond_faint_count.as_ref().map_or(true, |value| *value == faint_count)
+    }
+}
+
+#[async_trait]
+impl AsTriggerCondition for FaintCondition {
+    async fn check(&self, event: &Event, action_ctx: &ActionContext) -> bool {
+        let Event::PlayerFainted { faint_count } = event else {
+            return false;
+        };
+        if !self.matches(*faint_count) {
+            return false;
+        }
+        action_ctx.lock().await.insert("faint_count".to_string(), faint_count.to_string());
+        true
+    }
+
+    fn event_type(&self) -> EventType {
+        EventType::PlayerFainted
+    }
+}
+
+#[async_trait]
+impl AsCheckCondition for FaintCondition {
+    async fn check(&self, action_ctx: &ActionContext) -> bool {
+        let faint_count = self.shared_ctx.read().await.faint_count;
+        action_ctx.lock().await.insert("faint_count".to_string(), faint_count.to_string());
+        self.matches(faint_count)
+    }
+}
@@ -0,0 +1,212 @@
+use async_trait::async_trait;
+use log::error;
+
+use crate::{
+    actions::ActionContext,
+    configs::{CheckCondition, NewOldValueCmp, TriggerCondition, ValueCmp},
+    event::{Event, EventType},
+    game_context::Gunlance,
+    triggers::{AsCheckCondition, AsTriggerCondition, SharedContext},
+};
+
+use super::{compare_state_change, TimerCmp};
+
+/// 弹药数量比较条件
+enum ShellsCmp {
+    Value(NewOldValueCmp),
+    /// 弹药装满
+    Full,
+    /// 弹药打空
+    Empty,
+}
+
+impl ShellsCmp {
+    fn parse(value: Option<NewOldValueCmp>) -> Option<Self> {
+        let value = value?;
+        match &value.new {
+            Some(ValueCmp::Special(s)) => match s.as_str() {
+                "full" => Some(ShellsCmp::Full),
+                "empty" => Some(ShellsCmp::Empty),
+                _ => {
+                    error!("shells 不支持值 {}，已忽略该条件", s);
+                    None
+                }
+            },
+            _ => Some(ShellsCmp::Value(value)),
+        }
+    }
+
+    fn matches_change(&self, new: &Gunlance, old: &Gunlance) -> bool {
+        match self {
+            ShellsCmp::Value(value) => {
+                value.new.as_ref().map_or(true, |cmp| *cmp == new.shells)
+                    && value.old.as_ref().map_or(true, |cmp| *cmp == old.shells)
+            }
+            ShellsCmp::Full => new.shells == new.max_shells && old.shells != new.max_shells,
+            ShellsCmp::Empty => new.shells == 0 && old.shells != 0,
+        }
+    }
+
+    fn matches_state(&self, gunlance: &Gunlance) -> bool {
+        match self {
+            ShellsCmp::Value(value) => value.new.as_ref().map_or(true, |cmp| *cmp == gunlance.shells),
+            ShellsCmp::Full => gunlance.shells == gunlance.max_shells,
+            ShellsCmp::Empty => gunlance.shells == 0,
+        }
+    }
+}
+
+/// 铳枪条件
+pub struct GunlanceCondition {
+    shared_ctx: SharedContext,
+    cond_shells: Option<ShellsCmp>,
+    cond_stake_loaded: Option<bool>,
+    cond_wyvernfire_cooldown: Option<TimerCmp>,
+    cond_wyvernfire_ready: Option<bool>,
+    cond_wyrmstake_loaded: Option<bool>,
+}
+
+impl GunlanceCondition {
+    pub fn new_trigger(cond: &TriggerCondition, shared_ctx: SharedContext) -> Self {
+        let cond = cond.clone();
+        if let TriggerCondition::Gunlance {
+            shells,
+            stake_loaded,
+            wyvernfire_cooldown,
+            wyrmstake_loaded,
+        } = cond
+        {
+            GunlanceCondition {
+                shared_ctx,
+                cond_shells: ShellsCmp::parse(*shells),
+                cond_stake_loaded: stake_loaded,
+                cond_wyvernfire_cooldown: TimerCmp::parse(*wyvernfire_cooldown, None, "ready"),
+                cond_wyvernfire_ready: None,
+                cond_wyrmstake_loaded: wyrmstake_loaded,
+            }
+        } else {
+            error!("internal: GunlanceCondition cond 参数不正确");
+            panic!("internal: GunlanceCondition cond 参数不正确");
+        }
+    }
+
+    pub fn new_check(cond: &CheckCondition, shared_ctx: SharedContext) -> Self {
+        let cond = cond.clone();
+        if let CheckCondition::Gunlance {
+            shells,
+            stake_loaded,
+            wyvernfire_ready,
+            wyrmstake_loaded,
+        } = cond
+        {
+            GunlanceCondition {
+                shared_ctx,
+                // 检查条件只比较当前值
+                cond_shells: ShellsCmp::parse(shells.map(|shells| NewOldValueCmp {
+                    new: Some(shells),
+                    old: None,
+                })),
+                cond_stake_loaded: stake_loaded,
+                cond_wyvernfire_cooldown: None,
+                cond_wyvernfire_ready: wyvernfire_ready,
+                cond_wyrmstake_loaded: wyrmstake_loaded,
+            }
+        } else {
+            error!("internal: GunlanceCondition cond 参数不正确");
+            panic!("internal: GunlanceCondition cond 参数不正确");
+        }
+    }
+
+    fn matches_change(&self, new: &Gunlance, old: &Gunlance) -> bool {
+        self.cond_shells.as_ref().map_or(true, |cmp| cmp.matches_change(new, old))
+            && compare_state_change(self.cond_stake_loaded, new.stake_loaded, old.stake_loaded)
+            && self
+                .cond_wyvernfire_cooldown
+                .as_ref()
+                .map_or(true, |cmp| cmp.matches(new.wyvernfire_cooldown, old.wyvernfire_cooldown))
+            && compare_state_change(self.cond_wyrmstake_loaded, new.wyrmstake_loaded, old.wyrmstake_loaded)
+    }
+
+    fn matches_state(&self, gunlance: &Gunlance) -> bool {
+        self.cond_shells.as_ref().map_or(true, |cmp| cmp.matches_state(gunlance))
+            && self.cond_stake_loaded.map_or(true, |loaded| loaded == gunlance.stake_loaded)
+            && self.cond_wyvernfire_ready.map_or(true, |ready| ready == gunlance.is_wyvernfire_ready())
+            && self.cond_wyrmstake_loaded.map_or(true, |loaded| loaded == gunlance.wyrmstake_loaded)
+    }
+}
+
+async fn insert_gunlance(action_ctx: &ActionContext, gunlance: &Gunlance) {
+    let mut action_ctx = action_ctx.lock().await;
+    action_ctx.insert("shells".to_string(), gunlance.shells.to_string());
+    action_ctx.insert("max_shells".to_string(), gunlance.max_shells.to_string());
+    action_ctx.insert("wyvernfire_cooldown".to_string(), (gunlance.wyvernfire_cooldown.max(0.0) as i32).to_string());
+}
+
+#[async_trait]
+impl AsTriggerCondition for GunlanceCondition {
+    async fn check(&self, event: &Event, action_ctx: &ActionContext) -> bool {
+        if !matches!(event, Event::Gunlance) {
+            return false;
+        }
+        let ctx = self.shared_ctx.read().await;
+        let Some(last_ctx) = ctx.last_ctx.as_ref() else {
+            return false;
+        };
+        if !self.matches_change(&ctx.gunlance, &last_ctx.gunlance) {
+            return false;
+        }
+        insert_gunlance(action_ctx, &ctx.gunlance).await;
+        true
+    }
+
+    fn event_type(&self) -> EventType {
+        EventType::Gunlance
+    }
+}
+
+#[async_trait]
+impl AsCheckCondition for GunlanceCondition {
+    async fn check(&self, action_ctx: &ActionContext) -> bool {
+        let ctx = self.shared_ctx.read().await;
+        if ctx.weapon_type != 7 || !self.matches_state(&ctx.gunlance) {
+            return false;
+        }
+        insert_gunlance(action_ctx, &ctx.gunlance).await;
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gunlance(shells: i32) -> Gunlance {
+        Gunlance {
+            shells,
+            max_shells: 5,
+            ..Default::default()
+        }
+    }
+
+    fn shells_cmp(s: &str) -> Option<ShellsCmp> {
+        ShellsCmp::parse(Some(NewOldValueCmp {
+            new: Some(ValueCmp::Special(s.to_string())),
+            old: None,
+        }))
+    }
+
+    #[test]
+    fn test_shells_special() {
+        let full = shells_cmp("full").unwrap();
+        assert!(full.matches_change(&gunlance(5), &gunlance(0)));
+        assert!(!full.matches_change(&gunlance(5), &gunlance(5)));
+        assert!(full.matches_state(&gunlance(5)));
+
+        let empty = shells_cmp("empty").unwrap();
+        assert!(empty.matches_change(&gunlance(0), &gunlance(1)));
+        assert!(!empty.matches_change(&gunlance(1), &gunlance(2)));
+        assert!(!empty.matches_state(&gunlance(1)));
+
+        assert!(shells_cmp("half").is_none());
+    }
+}
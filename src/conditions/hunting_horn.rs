@@ -0,0 +1,195 @@
+use async_trait::async_trait;
+use log::error;
+
+use crate::{
+    actions::ActionContext,
+    configs::{CheckCondition, NewOldValueCmp, TriggerCondition, ValueCmp},
+    event::{Event, EventType},
+    game_context::{self, HuntingHorn},
+    triggers::{AsCheckCondition, AsTriggerCondition, SharedContext},
+};
+
+/// 旋律槽数量比较条件
+enum QueueSizeCmp {
+    Value(NewOldValueCmp),
+    /// 旋律槽已满
+    Full,
+}
+
+/// 狩猎笛条件
+pub struct HuntingHornCondition {
+    shared_ctx: SharedContext,
+    event_type: EventType,
+    cond_song: Option<ValueCmp>,
+    cond_queue_size: Option<QueueSizeCmp>,
+}
+
+impl HuntingHornCondition {
+    pub fn new_trigger(cond: &TriggerCondition, shared_ctx: SharedContext) -> Self {
+        let cond = cond.clone();
+        match cond {
+            TriggerCondition::HuntingHornSong { song } => HuntingHornCondition {
+                shared_ctx,
+                event_type: EventType::HuntingHornSongPerformed,
+                cond_song: parse_song(song),
+                cond_queue_size: None,
+            },
+            TriggerCondition::HuntingHornQueue { queue_size } => HuntingHornCondition {
+                shared_ctx,
+                event_type: EventType::HuntingHornQueueChanged,
+                cond_song: None,
+                cond_queue_size: parse_queue_size(*queue_size),
+            },
+            _ => {
+                error!("internal: HuntingHornCondition cond 参数不正确");
+                panic!("internal: HuntingHornCondition cond 参数不正确");
+            }
+        }
+    }
+
+    pub fn new_check(cond: &CheckCondition, shared_ctx: SharedContext) -> Self {
+        let cond = cond.clone();
+        if let CheckCondition::HuntingHorn {
+            queued_song,
+            queue_size,
+        } = cond
+        {
+            HuntingHornCondition {
+                shared_ctx,
+                event_type: EventType::HuntingHornQueueChanged,
+                cond_song: parse_song(queued_song),
+                // 检查条件只比较当前值
+                cond_queue_size: queue_size.map(|queue_size| {
+                    QueueSizeCmp::Value(NewOldValueCmp {
+                        new: Some(queue_size),
+                        old: None,
+                    })
+                }),
+            }
+        } else {
+            error!("internal: HuntingHornCondition cond 参数不正确");
+            panic!("internal: HuntingHornCondition cond 参数不正确");
+        }
+    }
+
+    fn matches_queue_change(&self, new: &HuntingHorn, old: &HuntingHorn) -> bool {
+        match &self.cond_queue_size {
+            None => true,
+            Some(QueueSizeCmp::Full) => new.is_queue_full() && !old.is_queue_full(),
+            Some(QueueSizeCmp::Value(value)) => {
+                value.new.as_ref().map_or(true, |cmp| *cmp == new.song_queue.len() as i32)
+                    && value.old.as_ref().map_or(true, |cmp| *cmp == old.song_queue.len() as i32)
+            }
+        }
+    }
+
+    fn matches_state(&self, hunting_horn: &HuntingHorn) -> bool {
+        let song_matched = self
+            .cond_song
+            .as_ref()
+            .map_or(true, |song| hunting_horn.song_queue.iter().any(|song_id| *song == *song_id));
+        let size_matched = match &self.cond_queue_size {
+            None => true,
+            Some(QueueSizeCmp::Full) => hunting_horn.is_queue_full(),
+            Some(QueueSizeCmp::Value(value)) => {
+                value.new.as_ref().map_or(true, |cmp| *cmp == hunting_horn.song_queue.len() as i32)
+            }
+        };
+        song_matched && size_matched
+    }
+}
+
+/// 将旋律名称转换为旋律ID
+///
+/// 旋律名称已在加载配置时检查
+fn parse_song(value: Option<ValueCmp>) -> Option<ValueCmp> {
+    match value {
+        Some(ValueCmp::Special(name)) => {
+            let Some(def) = game_context::find_song(&name) else {
+                error!("internal: 旋律 {} 无定义", name);
+                panic!("internal: 旋律 {} 无定义", name);
+            };
+            Some(ValueCmp::EqInt(def.id))
+        }
+        other => other,
+    }
+}
+
+fn parse_queue_size(value: Option<NewOldValueCmp>) -> Option<QueueSizeCmp> {
+    let value = value?;
+    match &value.new {
+        Some(ValueCmp::Special(s)) if s == "full" => Some(QueueSizeCmp::Full),
+        Some(ValueCmp::Special(s)) => {
+            error!("queue_size 不支持值 {}，已忽略该条件", s);
+            None
+        }
+        _ => Some(QueueSizeCmp::Value(value)),
+    }
+}
+
+/// 旋律名称，未定义的旋律使用ID
+fn song_display_name(song_id: i32) -> String {
+    game_context::find_song_by_id(song_id).map_or_else(|| song_id.to_string(), |def| def.display_name.to_string())
+}
+
+#[async_trait]
+impl AsTriggerCondition for HuntingHornCondition {
+    async fn check(&self, event: &Event, action_ctx: &ActionContext) -> bool {
+        match event {
+            Event::HuntingHornSongPerformed { song_id } => {
+                if !self.cond_song.as_ref().map_or(true, |song| *song == *song_id) {
+                    return false;
+                }
+                let mut action_ctx = action_ctx.lock().await;
+                action_ctx.insert("song_id".to_string(), song_id.to_string());
+                action_ctx.insert("song".to_string(), song_display_name(*song_id));
+                true
+            }
+            Event::HuntingHornQueueChanged => {
+                let ctx = self.shared_ctx.read().await;
+                let Some(last_ctx) = ctx.last_ctx.as_ref() else {
+                    return false;
+                };
+                if !self.matches_queue_change(&ctx.hunting_horn, &last_ctx.hunting_horn) {
+                    return false;
+                }
+                let mut action_ctx = action_ctx.lock().await;
+                action_ctx.insert("queue_size".to_string(), ctx.hunting_horn.song_queue.len().to_string());
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn event_type(&self) -> EventType {
+        self.event_type.clone()
+    }
+}
+
+#[async_trait]
+impl AsCheckCondition for HuntingHornCondition {
+    async fn check(&self, action_ctx: &ActionContext) -> bool {
+        let ctx = self.shared_ctx.read().await;
+        if ctx.weapon_type != 5 || !self.matches_state(&ctx.hunting_horn) {
+            return false;
+        }
+        let mut action_ctx = action_ctx.lock().await;
+        action_ctx.insert("queue_size".to_string(), ctx.hunting_horn.song_queue.len().to_string());
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_song_name() {
+        let def = game_context::find_song("attack_up").unwrap();
+        assert_eq!(def.id, 1);
+        assert!(matches!(
+            parse_song(Some(ValueCmp::Special("attack_up".to_string()))),
+            Some(ValueCmp::EqInt(id)) if id == def.id
+        ));
+    }
+}
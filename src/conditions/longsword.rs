@@ -3,22 +3,26 @@ use log::error;
 
 use crate::{
     actions::ActionContext,
-    configs::{CheckCondition, TriggerCondition},
+    configs::{CheckCondition, NewOldValueCmp, TriggerCondition, ValueCmp},
     event::{Event, EventType},
     triggers::{AsCheckCondition, AsTriggerCondition, SharedContext},
 };
 
-use super::{CheckFn, TriggerFn};
+use super::{compare_new_old, CheckFn, TriggerFn};
 
 pub struct LongswordCondition {
     trigger_fn: TriggerFn,
     check_fn: CheckFn,
     shared_ctx: SharedContext,
+    event_type: EventType,
 }
 
 impl LongswordCondition {
     pub fn new_trigger(cond: &TriggerCondition, shared_ctx: SharedContext) -> Self {
         let cond = cond.clone();
+        if let TriggerCondition::LongswordSpirit { gauge, level_timer } = cond {
+            return Self::new_spirit_trigger(*gauge, *level_timer, shared_ctx);
+        }
         let trigger_fn: TriggerFn = if let TriggerCondition::LongswordLevelChanged { new, old } = cond {
             Box::new(move |event| {
                 if let Event::LongswordLevelChanged {
@@ -50,6 +54,49 @@ impl LongswordCondition {
             trigger_fn,
             check_fn: Box::new(|_| false),
             shared_ctx,
+            event_type: EventType::LongswordLevelChanged,
+        }
+    }
+
+    fn new_spirit_trigger(
+        gauge: Option<NewOldValueCmp>,
+        level_timer: Option<NewOldValueCmp>,
+        shared_ctx: SharedContext,
+    ) -> Self {
+        let mut gauge_full = false;
+        if let Some(NewOldValueCmp {
+            new: Some(ValueCmp::Special(s)),
+            ..
+        }) = &gauge
+        {
+            if s != "full" {
+                error!("gauge 不支持值 {}，已拒绝条件", s);
+                return LongswordCondition {
+                    trigger_fn: Box::new(|_| false),
+                    check_fn: Box::new(|_| false),
+                    shared_ctx,
+                    event_type: EventType::LongswordSpiritChanged,
+                };
+            }
+            gauge_full = true;
+        }
+        // 特殊值单独判断
+        let gauge = if gauge_full { None } else { gauge };
+        let trigger_fn: TriggerFn = Box::new(move |event| {
+            if let Event::LongswordSpiritChanged { new, old } = event {
+                (!gauge_full || (new.is_gauge_full() && !old.is_gauge_full()))
+                    && compare_new_old(&gauge, new.spirit_gauge as i32, old.spirit_gauge as i32)
+                    && compare_new_old(&level_timer, new.level_timer as i32, old.level_timer as i32)
+            } else {
+                false
+            }
+        });
+
+        LongswordCondition {
+            trigger_fn,
+            check_fn: Box::new(|_| false),
+            shared_ctx,
+            event_type: EventType::LongswordSpiritChanged,
         }
     }
 
@@ -62,6 +109,14 @@ impl LongswordCondition {
                 };
                 value == ctx.longsword_level
             })
+        } else if let CheckCondition::LongswordSpirit { gauge, level_timer } = cond {
+            Box::new(move |ctx| {
+                if ctx.weapon_type != 3 {
+                    return false;
+                };
+                gauge.as_ref().map_or(true, |gauge| *gauge == ctx.longsword.spirit_gauge as i32)
+                    && level_timer.as_ref().map_or(true, |level_timer| *level_timer == ctx.longsword.level_timer as i32)
+            })
         } else {
             error!("internal: LongswordCondition cmp_fn 参数不正确");
             Box::new(|_| false)
@@ -71,18 +126,27 @@ impl LongswordCondition {
             trigger_fn: Box::new(|_| false),
             check_fn,
             shared_ctx,
+            event_type: EventType::LongswordLevelChanged,
         }
     }
 }
 
 #[async_trait]
 impl AsTriggerCondition for LongswordCondition {
-    async fn check(&self, event: &Event, _action_ctx: &ActionContext) -> bool {
-        (self.trigger_fn)(event)
+    async fn check(&self, event: &Event, action_ctx: &ActionContext) -> bool {
+        if !(self.trigger_fn)(event) {
+            return false;
+        }
+        if let Event::LongswordSpiritChanged { new, .. } = event {
+            let mut action_ctx = action_ctx.lock().await;
+            action_ctx.insert("spirit_gauge".to_string(), (new.spirit_gauge as i32).to_string());
+            action_ctx.insert("level_timer".to_string(), (new.level_timer.max(0.0) as i32).to_string());
+        }
+        true
     }
 
     fn event_type(&self) -> EventType {
-        EventType::LongswordLevelChanged
+        self.event_type.clone()
     }
 }
 
@@ -92,3 +156,41 @@ impl AsCheckCondition for LongswordCondition {
         (self.check_fn)(&*self.shared_ctx.read().await)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use tokio::sync::RwLock;
+
+    use super::*;
+    use crate::game_context::{Context, Longsword};
+
+    fn spirit_event(new_gauge: f32, old_gauge: f32) -> Event {
+        Event::LongswordSpiritChanged {
+            new: Longsword {
+                spirit_gauge: new_gauge,
+                level_timer: 0.0,
+            },
+            old: Longsword {
+                spirit_gauge: old_gauge,
+                level_timer: 0.0,
+            },
+        }
+    }
+
+    #[test]
+    fn test_spirit_gauge_full() {
+        let cond = TriggerCondition::LongswordSpirit {
+            gauge: Box::new(Some(NewOldValueCmp {
+                new: Some(ValueCmp::Special("full".to_string())),
+                old: None,
+            })),
+            level_timer: Box::new(None),
+        };
+        let cond = LongswordCondition::new_trigger(&cond, Arc::new(RwLock::new(Context::default())));
+        assert!((cond.trigger_fn)(&spirit_event(100.0, 97.0)));
+        assert!(!(cond.trigger_fn)(&spirit_event(100.0, 100.0)));
+        assert!(!(cond.trigger_fn)(&spirit_event(97.0, 100.0)));
+    }
+}
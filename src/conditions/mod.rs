@@ -1,11 +1,112 @@
 pub mod charge_blade;
+pub mod charge_level;
+pub mod count;
 pub mod damage;
+pub mod dual_blades;
+pub mod faint;
 pub mod fsm;
+pub mod gunlance;
+pub mod hunting_horn;
 pub mod insect_glaive;
 pub mod longsword;
+pub mod monster;
+pub mod monster_state;
+pub mod personal_best;
+pub mod player_vitals;
 pub mod quest_state;
+pub mod quest_summary;
+pub mod ranged;
+pub mod sharpness;
+pub mod specialized_tool;
+pub mod status_effect;
+pub mod switch_axe;
 pub mod use_item;
 pub mod weapon_id;
 
+use log::error;
+
+use crate::configs::{NewOldValueCmp, ValueCmp};
+
 type TriggerFn = Box<dyn Fn(&crate::event::Event) -> bool + Send + Sync>;
 type CheckFn = Box<dyn Fn(&crate::game_context::Context) -> bool + Send + Sync>;
+
+/// 比较新旧值，未设置的条件视为满足
+fn compare_new_old(cfg_value: &Option<NewOldValueCmp>, ctx_new: i32, ctx_old: i32) -> bool {
+    let Some(cfg_value) = cfg_value else {
+        return true;
+    };
+    cfg_value.new.as_ref().map_or(true, |new| *new == ctx_new)
+        && cfg_value.old.as_ref().map_or(true, |old| *old == ctx_old)
+}
+
+/// 比较布尔状态的变化
+///
+/// `true` 表示进入该状态，`false` 表示离开该状态，未设置的条件视为满足
+fn compare_state_change(cfg_value: Option<bool>, ctx_new: bool, ctx_old: bool) -> bool {
+    cfg_value.map_or(true, |value| ctx_new == value && ctx_old != value)
+}
+
+/// 计时器比较条件
+enum TimerCmp {
+    Value(NewOldValueCmp),
+    /// 计时器开始
+    Started,
+    /// 计时器结束
+    Finished,
+}
+
+impl TimerCmp {
+    /// 解析计时器条件，`started` 和 `finished` 为对应含义的特殊值
+    fn parse(value: Option<NewOldValueCmp>, started: Option<&str>, finished: &str) -> Option<Self> {
+        let value = value?;
+        match &value.new {
+            Some(ValueCmp::Special(s)) if Some(s.as_str()) == started => Some(TimerCmp::Started),
+            Some(ValueCmp::Special(s)) if s == finished => Some(TimerCmp::Finished),
+            Some(ValueCmp::Special(s)) => {
+                error!("计时器条件不支持值 {}，已忽略该条件", s);
+                None
+            }
+            _ => Some(TimerCmp::Value(value)),
+        }
+    }
+
+    fn matches(&self, new: f32, old: f32) -> bool {
+        match self {
+            TimerCmp::Value(value) => {
+                value.new.as_ref().map_or(true, |cmp| *cmp == new as i32)
+                    && value.old.as_ref().map_or(true, |cmp| *cmp == old as i32)
+            }
+            TimerCmp::Started => new > 0.0 && old <= 0.0,
+            TimerCmp::Finished => new <= 0.0 && old > 0.0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn timer_cmp(new: &str) -> Option<NewOldValueCmp> {
+        Some(NewOldValueCmp {
+            new: Some(ValueCmp::Special(new.to_string())),
+            old: None,
+        })
+    }
+
+    #[test]
+    fn test_timer_special() {
+        let activated = TimerCmp::parse(timer_cmp("activated"), Some("activated"), "expired").unwrap();
+        assert!(activated.matches(90.0, 0.0));
+        assert!(!activated.matches(89.0, 90.0));
+
+        let expired = TimerCmp::parse(timer_cmp("expired"), Some("activated"), "expired").unwrap();
+        assert!(expired.matches(0.0, 0.5));
+        assert!(!expired.matches(0.0, 0.0));
+
+        let ready = TimerCmp::parse(timer_cmp("ready"), None, "ready").unwrap();
+        assert!(ready.matches(0.0, 0.2));
+        assert!(!ready.matches(0.2, 0.4));
+
+        assert!(TimerCmp::parse(timer_cmp("unknown"), Some("activated"), "expired").is_none());
+    }
+}
@@ -0,0 +1,73 @@
+use async_trait::async_trait;
+use log::error;
+
+use crate::{
+    actions::ActionContext,
+    configs::{MonsterEvent, TriggerCondition, ValueCmp},
+    event::{Event, EventType},
+    game::DamageCollector,
+    triggers::AsTriggerCondition,
+};
+
+/// 怪物出现/消失条件
+///
+/// 依赖钩子功能提供的怪物创建和销毁事件
+pub struct MonsterCondition {
+    cond_event: MonsterEvent,
+    cond_monster_id: Option<ValueCmp>,
+}
+
+impl MonsterCondition {
+    pub fn new_trigger(cond: &TriggerCondition) -> Self {
+        let cond = cond.clone();
+        if let TriggerCondition::Monster { event, monster_id } = cond {
+            MonsterCondition {
+                cond_event: event,
+                cond_monster_id: monster_id,
+            }
+        } else {
+            error!("internal: MonsterCondition cond 参数不正确");
+            panic!("internal: MonsterCondition cond 参数不正确");
+        }
+    }
+}
+
+#[async_trait]
+impl AsTriggerCondition for MonsterCondition {
+    async fn check(&self, event: &Event, action_ctx: &ActionContext) -> bool {
+        let (monster, monster_id) = match (event, &self.cond_event) {
+            (
+                Event::MonsterSpawned {
+                    monster, monster_id, ..
+                },
+                MonsterEvent::Spawned,
+            ) => (*monster, *monster_id),
+            (Event::MonsterDespawned { monster, monster_id }, MonsterEvent::Despawned) => (*monster, *monster_id),
+            _ => return false,
+        };
+        if let Some(cond_monster_id) = &self.cond_monster_id {
+            if *cond_monster_id != monster_id {
+                return false;
+            }
+        }
+
+        let damage_collector = DamageCollector::instance();
+        let monster_count = damage_collector.live_monster_count().await;
+        let monster_damage = damage_collector.monster_damage(monster).await;
+        let mut action_ctx = action_ctx.lock().await;
+        action_ctx.insert("monster_id".to_string(), monster_id.to_string());
+        action_ctx.insert("monster_count".to_string(), monster_count.to_string());
+        if let Some((monster, monster_damage)) = monster_damage {
+            action_ctx.insert("monster".to_string(), monster.to_string());
+            action_ctx.insert("monster_damage".to_string(), monster_damage.to_string());
+        }
+        true
+    }
+
+    fn event_type(&self) -> EventType {
+        match self.cond_event {
+            MonsterEvent::Spawned => EventType::MonsterSpawned,
+            MonsterEvent::Despawned => EventType::MonsterDespawned,
+        }
+    }
+}
@@ -0,0 +1,134 @@
+use async_trait::async_trait;
+use log::error;
+
+use crate::{
+    actions::ActionContext,
+    configs::{CheckCondition, NewOldValueCmp, TriggerCondition, ValueCmp},
+    event::{Event, EventType},
+    game_context::Monster,
+    triggers::{AsCheckCondition, AsTriggerCondition, SharedContext},
+};
+
+use super::{compare_new_old, compare_state_change};
+
+/// 怪物状态条件
+pub struct MonsterStateCondition {
+    shared_ctx: SharedContext,
+    cond_monster_id: Option<ValueCmp>,
+    cond_health: Option<NewOldValueCmp>,
+    cond_enraged: Option<bool>,
+    cond_exhausted: Option<bool>,
+    cond_trapped: Option<bool>,
+}
+
+impl MonsterStateCondition {
+    pub fn new_trigger(cond: &TriggerCondition, shared_ctx: SharedContext) -> Self {
+        let cond = cond.clone();
+        if let TriggerCondition::MonsterState {
+            monster_id,
+            health,
+            enraged,
+            exhausted,
+            trapped,
+        } = cond
+        {
+            MonsterStateCondition {
+                shared_ctx,
+                cond_monster_id: monster_id,
+                cond_health: *health,
+                cond_enraged: enraged,
+                cond_exhausted: exhausted,
+                cond_trapped: trapped,
+            }
+        } else {
+            error!("internal: MonsterStateCondition cond 参数不正确");
+            panic!("internal: MonsterStateCondition cond 参数不正确");
+        }
+    }
+
+    pub fn new_check(cond: &CheckCondition, shared_ctx: SharedContext) -> Self {
+        let cond = cond.clone();
+        if let CheckCondition::MonsterState {
+            monster_id,
+            health,
+            enraged,
+            exhausted,
+            trapped,
+        } = cond
+        {
+            MonsterStateCondition {
+                shared_ctx,
+                cond_monster_id: monster_id,
+                // 检查条件只比较当前值
+                cond_health: health.map(|health| NewOldValueCmp {
+                    new: Some(health),
+                    old: None,
+                }),
+                cond_enraged: enraged,
+                cond_exhausted: exhausted,
+                cond_trapped: trapped,
+            }
+        } else {
+            error!("internal: MonsterStateCondition cond 参数不正确");
+            panic!("internal: MonsterStateCondition cond 参数不正确");
+        }
+    }
+
+    fn matches_monster_id(&self, monster: &Monster) -> bool {
+        self.cond_monster_id.as_ref().map_or(true, |monster_id| *monster_id == monster.monster_id)
+    }
+
+    fn matches_change(&self, new: &Monster, old: &Monster) -> bool {
+        self.matches_monster_id(new)
+            && compare_new_old(&self.cond_health, new.health_percent(), old.health_percent())
+            && compare_state_change(self.cond_enraged, new.is_enraged, old.is_enraged)
+            && compare_state_change(self.cond_exhausted, new.is_exhausted, old.is_exhausted)
+            && compare_state_change(self.cond_trapped, new.is_trapped, old.is_trapped)
+    }
+
+    fn matches_state(&self, monster: &Monster) -> bool {
+        self.matches_monster_id(monster)
+            && compare_new_old(&self.cond_health, monster.health_percent(), 0)
+            && self.cond_enraged.map_or(true, |enraged| enraged == monster.is_enraged)
+            && self.cond_exhausted.map_or(true, |exhausted| exhausted == monster.is_exhausted)
+            && self.cond_trapped.map_or(true, |trapped| trapped == monster.is_trapped)
+    }
+}
+
+#[async_trait]
+impl AsTriggerCondition for MonsterStateCondition {
+    async fn check(&self, event: &Event, action_ctx: &ActionContext) -> bool {
+        let Event::MonsterStateChanged { monster } = event else {
+            return false;
+        };
+        let ctx = self.shared_ctx.read().await;
+        let Some(last_ctx) = ctx.last_ctx.as_ref() else {
+            return false;
+        };
+        let new = ctx.monsters.iter().find(|m| m.address == *monster);
+        let old = last_ctx.monsters.iter().find(|m| m.address == *monster);
+        let (Some(new), Some(old)) = (new, old) else {
+            return false;
+        };
+        if !self.matches_change(new, old) {
+            return false;
+        }
+
+        let mut action_ctx = action_ctx.lock().await;
+        action_ctx.insert("monster_id".to_string(), new.monster_id.to_string());
+        action_ctx.insert("monster_health".to_string(), new.health_percent().to_string());
+        true
+    }
+
+    fn event_type(&self) -> EventType {
+        EventType::MonsterStateChanged
+    }
+}
+
+#[async_trait]
+impl AsCheckCondition for MonsterStateCondition {
+    async fn check(&self, _action_ctx: &ActionContext) -> bool {
+        let ctx = self.shared_ctx.read().await;
+        ctx.monsters.iter().any(|monster| self.matches_state(monster))
+    }
+}
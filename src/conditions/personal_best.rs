@@ -0,0 +1,70 @@
+use async_trait::async_trait;
+use log::error;
+use tokio::sync::Mutex;
+
+use crate::{actions::ActionContext, configs::CheckCondition, personal_best::PersonalBest, triggers::AsCheckCondition};
+
+/// 个人最佳记录条件
+///
+/// 使用之前的伤害检查条件的结果（`damage` 和 `move`）与个人最佳记录比较，
+/// 触发器实际触发后才更新记录
+pub struct PersonalBestCondition {
+    cond_is_pb: Option<bool>,
+    cond_key: Option<String>,
+    /// 待提交的记录
+    candidate: Mutex<Option<(String, i32)>>,
+}
+
+impl PersonalBestCondition {
+    pub fn new_check(cond: &CheckCondition) -> Self {
+        let cond = cond.clone();
+        if let CheckCondition::PersonalBest { is_pb, key } = cond {
+            PersonalBestCondition {
+                cond_is_pb: is_pb,
+                cond_key: key,
+                candidate: Mutex::new(None),
+            }
+        } else {
+            error!("internal: PersonalBestCondition cond 参数不正确");
+            panic!("internal: PersonalBestCondition cond 参数不正确");
+        }
+    }
+}
+
+#[async_trait]
+impl AsCheckCondition for PersonalBestCondition {
+    async fn check(&self, action_ctx: &ActionContext) -> bool {
+        let (damage, key) = {
+            let action_ctx = action_ctx.lock().await;
+            let damage = action_ctx.get("damage").and_then(|v| v.parse::<i32>().ok());
+            let key = self.cond_key.clone().or_else(|| action_ctx.get("move").cloned());
+            (damage, key)
+        };
+        let (Some(damage), Some(key)) = (damage, key) else {
+            error!("personal_best 需要在 damage 检查条件之后使用");
+            return false;
+        };
+
+        let result = PersonalBest::instance().compare(&key, damage).await;
+        if result.is_pb {
+            *self.candidate.lock().await = Some((key, damage));
+        }
+        {
+            let mut action_ctx = action_ctx.lock().await;
+            action_ctx.insert("pb".to_string(), result.pb.to_string());
+            action_ctx.insert("prev_pb".to_string(), result.prev_pb.to_string());
+            action_ctx.insert("is_pb".to_string(), result.is_pb.to_string());
+        }
+        self.cond_is_pb.map_or(true, |is_pb| is_pb == result.is_pb)
+    }
+
+    async fn on_triggered(&self) {
+        *self.candidate.lock().await = None;
+    }
+
+    async fn on_fired(&self) {
+        if let Some((key, damage)) = self.candidate.lock().await.take() {
+            PersonalBest::instance().commit(&key, damage).await;
+        }
+    }
+}
@@ -0,0 +1,138 @@
+use async_trait::async_trait;
+use log::error;
+
+use crate::{
+    actions::ActionContext,
+    configs::{CheckCondition, NewOldValueCmp, TriggerCondition, ValueCmp},
+    event::{Event, EventType},
+    game_context::Context,
+    triggers::{AsCheckCondition, AsTriggerCondition, SharedContext},
+};
+
+use super::compare_new_old;
+
+/// 玩家体力和耐力条件
+pub struct PlayerVitalsCondition {
+    shared_ctx: SharedContext,
+    cond_health: Option<NewOldValueCmp>,
+    cond_health_lost: Option<ValueCmp>,
+    cond_red_health: Option<ValueCmp>,
+    cond_stamina: Option<NewOldValueCmp>,
+}
+
+impl PlayerVitalsCondition {
+    pub fn new_trigger(cond: &TriggerCondition, shared_ctx: SharedContext) -> Self {
+        let cond = cond.clone();
+        if let TriggerCondition::PlayerVitals {
+            health,
+            health_lost,
+            stamina,
+        } = cond
+        {
+            PlayerVitalsCondition {
+                shared_ctx,
+                cond_health: *health,
+                cond_health_lost: health_lost,
+                cond_red_health: None,
+                cond_stamina: *stamina,
+            }
+        } else {
+            error!("internal: PlayerVitalsCondition cond 参数不正确");
+            panic!("internal: PlayerVitalsCondition cond 参数不正确");
+        }
+    }
+
+    pub fn new_check(cond: &CheckCondition, shared_ctx: SharedContext) -> Self {
+        let cond = cond.clone();
+        if let CheckCondition::PlayerVitals {
+            health,
+            red_health,
+            stamina,
+        } = cond
+        {
+            // 检查条件只比较当前值
+            let new_only = |value: Option<ValueCmp>| {
+                value.map(|new| NewOldValueCmp {
+                    new: Some(new),
+                    old: None,
+                })
+            };
+            PlayerVitalsCondition {
+                shared_ctx,
+                cond_health: new_only(health),
+                cond_health_lost: None,
+                cond_red_health: red_health,
+                cond_stamina: new_only(stamina),
+            }
+        } else {
+            error!("internal: PlayerVitalsCondition cond 参数不正确");
+            panic!("internal: PlayerVitalsCondition cond 参数不正确");
+        }
+    }
+
+    fn matches(&self, new: &Context, old: &Context) -> bool {
+        if let Some(cond_health_lost) = &self.cond_health_lost {
+            let health_lost = health_lost_percent(new, old);
+            if health_lost <= 0 || *cond_health_lost != health_lost {
+                return false;
+            }
+        }
+        if let Some(cond_red_health) = &self.cond_red_health {
+            if *cond_red_health != new.red_health as i32 {
+                return false;
+            }
+        }
+        compare_new_old(&self.cond_health, new.health_percent(), old.health_percent())
+            && compare_new_old(&self.cond_stamina, new.stamina as i32, old.stamina as i32)
+    }
+}
+
+/// 单次变化损失的体力占最大体力的百分比
+fn health_lost_percent(new: &Context, old: &Context) -> i32 {
+    if old.max_health <= 0.0 {
+        return 0;
+    }
+    ((old.health - new.health) / old.max_health * 100.0) as i32
+}
+
+async fn insert_vitals(action_ctx: &ActionContext, new: &Context, old: &Context) {
+    let mut action_ctx = action_ctx.lock().await;
+    action_ctx.insert("health".to_string(), (new.health as i32).to_string());
+    action_ctx.insert("max_health".to_string(), (new.max_health as i32).to_string());
+    action_ctx.insert("health_percent".to_string(), new.health_percent().to_string());
+    action_ctx.insert("red_health".to_string(), (new.red_health as i32).to_string());
+    action_ctx.insert("stamina".to_string(), (new.stamina as i32).to_string());
+    action_ctx.insert("health_lost".to_string(), health_lost_percent(new, old).max(0).to_string());
+}
+
+#[async_trait]
+impl AsTriggerCondition for PlayerVitalsCondition {
+    async fn check(&self, event: &Event, action_ctx: &ActionContext) -> bool {
+        if !matches!(event, Event::PlayerVitals) {
+            return false;
+        }
+        let ctx = self.shared_ctx.read().await;
+        let Some(last_ctx) = ctx.last_ctx.as_ref() else {
+            return false;
+        };
+        if !self.matches(&ctx, last_ctx) {
+            return false;
+        }
+        insert_vitals(action_ctx, &ctx, last_ctx).await;
+        true
+    }
+
+    fn event_type(&self) -> EventType {
+        EventType::PlayerVitals
+    }
+}
+
+#[async_trait]
+impl AsCheckCondition for PlayerVitalsCondition {
+    async fn check(&self, action_ctx: &ActionContext) -> bool {
+        let ctx = self.shared_ctx.read().await;
+        let old = ctx.last_ctx.as_deref().unwrap_or(&ctx);
+        insert_vitals(action_ctx, &ctx, old).await;
+        self.matches(&ctx, &ctx)
+    }
+}
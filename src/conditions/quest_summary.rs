@@ -0,0 +1,78 @@
+use async_trait::async_trait;
+use log::error;
+
+use crate::{
+    actions::ActionContext,
+    configs::{TriggerCondition, ValueCmp},
+    event::{Event, EventType},
+    game::DamageCollector,
+    triggers::AsTriggerCondition,
+};
+
+/// 任务伤害总结条件
+///
+/// 任务完成时触发，写入本次任务的伤害统计
+pub struct QuestSummaryCondition {
+    cond_total_damage: Option<ValueCmp>,
+}
+
+impl QuestSummaryCondition {
+    pub fn new_trigger(cond: &TriggerCondition) -> Self {
+        let cond = cond.clone();
+        if let TriggerCondition::QuestSummary { total_damage } = cond {
+            QuestSummaryCondition {
+                cond_total_damage: total_damage,
+            }
+        } else {
+            error!("internal: QuestSummaryCondition cond 参数不正确");
+            panic!("internal: QuestSummaryCondition cond 参数不正确");
+        }
+    }
+}
+
+#[async_trait]
+impl AsTriggerCondition for QuestSummaryCondition {
+    async fn check(&self, event: &Event, action_ctx: &ActionContext) -> bool {
+        // 任务完成
+        if !matches!(event, Event::QuestStateChanged { new: 3, .. }) {
+            return false;
+        }
+        let summary = DamageCollector::instance().quest_summary().await;
+        if let Some(total_damage) = &self.cond_total_damage {
+            if *total_damage != i32::try_from(summary.total_damage).unwrap_or(i32::MAX) {
+                return false;
+            }
+        }
+
+        let quest_time = summary.quest_time as i64;
+        let mut action_ctx = action_ctx.lock().await;
+        action_ctx.insert("total_damage".to_string(), summary.total_damage.to_string());
+        action_ctx.insert("dps".to_string(), format!("{:.1}", summary.dps));
+        action_ctx.insert("quest_time".to_string(), format!("{:02}:{:02}", quest_time / 60, quest_time % 60));
+        action_ctx.insert("hits".to_string(), summary.hits.to_string());
+        action_ctx.insert("max_hit".to_string(), summary.max_hit.to_string());
+        action_ctx.insert("max_hit_move".to_string(), summary.max_hit_move.clone());
+        let monster_summary = summary
+            .monsters
+            .iter()
+            .enumerate()
+            .map(|(idx, damage)| format!("#{} {}", idx + 1, damage))
+            .collect::<Vec<_>>()
+            .join(" / ");
+        action_ctx.insert("monster_count".to_string(), summary.monsters.len().to_string());
+        action_ctx.insert("monster_summary".to_string(), monster_summary);
+        for idx in 0..3 {
+            let (name, damage) = match summary.top_moves.get(idx) {
+                Some((name, damage)) => (name.clone(), damage.to_string()),
+                None => ("-".to_string(), "0".to_string()),
+            };
+            action_ctx.insert(format!("top{}_move", idx + 1), name);
+            action_ctx.insert(format!("top{}_damage", idx + 1), damage);
+        }
+        true
+    }
+
+    fn event_type(&self) -> EventType {
+        EventType::QuestStateChanged
+    }
+}
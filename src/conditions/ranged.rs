@@ -0,0 +1,244 @@
+use async_trait::async_trait;
+use log::error;
+use mhw_toolkit::game::resources::WeaponType;
+
+use crate::{
+    actions::ActionContext,
+    configs::{CheckCondition, NewOldValueCmp, TriggerCondition, ValueCmp},
+    event::{Event, EventType},
+    game_context::{self, is_ranged_weapon, AmmoDef, Context, RangedWeapon},
+    triggers::{AsCheckCondition, AsTriggerCondition, SharedContext},
+};
+
+use super::compare_new_old;
+
+/// 弹药类型比较条件
+enum AmmoCmp {
+    Value(ValueCmp),
+    /// 使用弹药名称指定
+    Named(&'static AmmoDef),
+}
+
+impl AmmoCmp {
+    /// 解析弹药条件，未知的弹药名称将拒绝条件
+    fn parse(value: ValueCmp) -> Self {
+        match value {
+            ValueCmp::Special(name) => match game_context::find_ammo(&name) {
+                Some(def) => AmmoCmp::Named(def),
+                None => {
+                    error!("弹药 {} 无定义，已拒绝条件", name);
+                    AmmoCmp::Value(ValueCmp::Special(name))
+                }
+            },
+            value => AmmoCmp::Value(value),
+        }
+    }
+
+    fn matches(&self, ammo_type: i32, is_bow: bool) -> bool {
+        match self {
+            AmmoCmp::Value(value) => *value == ammo_type,
+            // 弓的瓶和弩的弹药ID会重复，需要同时判断武器类型
+            AmmoCmp::Named(def) => def.is_coating == is_bow && def.id == ammo_type,
+        }
+    }
+}
+
+/// 装填数量比较条件
+enum LoadedCmp {
+    Value(NewOldValueCmp),
+    /// 打空
+    Empty,
+    /// 装填
+    Reloaded,
+}
+
+impl LoadedCmp {
+    fn parse(value: Option<NewOldValueCmp>) -> Option<Self> {
+        let value = value?;
+        match &value.new {
+            Some(ValueCmp::Special(s)) => match s.as_str() {
+                "empty" => Some(LoadedCmp::Empty),
+                "reloaded" => Some(LoadedCmp::Reloaded),
+                _ => {
+                    error!("loaded 不支持值 {}，已忽略该条件", s);
+                    None
+                }
+            },
+            _ => Some(LoadedCmp::Value(value)),
+        }
+    }
+
+    fn matches(&self, new: &RangedWeapon, old: &RangedWeapon) -> bool {
+        match self {
+            LoadedCmp::Value(value) => {
+                value.new.as_ref().map_or(true, |cmp| *cmp == new.loaded)
+                    && value.old.as_ref().map_or(true, |cmp| *cmp == old.loaded)
+            }
+            // 切换弹药时装填数量也会变化，不视为打空或装填
+            LoadedCmp::Empty => new.ammo_type == old.ammo_type && new.loaded == 0 && old.loaded > 0,
+            LoadedCmp::Reloaded => new.ammo_type == old.ammo_type && new.loaded > old.loaded,
+        }
+    }
+}
+
+/// 弓和弩条件
+pub struct RangedWeaponCondition {
+    shared_ctx: SharedContext,
+    cond_ammo_type: Option<AmmoCmp>,
+    cond_ammo_changed: Option<bool>,
+    cond_loaded: Option<LoadedCmp>,
+    cond_reserve: Option<NewOldValueCmp>,
+    cond_charge_level: Option<NewOldValueCmp>,
+}
+
+impl RangedWeaponCondition {
+    pub fn new_trigger(cond: &TriggerCondition, shared_ctx: SharedContext) -> Self {
+        let cond = cond.clone();
+        if let TriggerCondition::RangedWeapon {
+            ammo_type,
+            ammo_changed,
+            loaded,
+            reserve,
+            charge_level,
+        } = cond
+        {
+            RangedWeaponCondition {
+                shared_ctx,
+                cond_ammo_type: ammo_type.map(AmmoCmp::parse),
+                cond_ammo_changed: ammo_changed,
+                cond_loaded: LoadedCmp::parse(*loaded),
+                cond_reserve: *reserve,
+                cond_charge_level: *charge_level,
+            }
+        } else {
+            error!("internal: RangedWeaponCondition cond 参数不正确");
+            panic!("internal: RangedWeaponCondition cond 参数不正确");
+        }
+    }
+
+    pub fn new_check(cond: &CheckCondition, shared_ctx: SharedContext) -> Self {
+        let cond = cond.clone();
+        if let CheckCondition::RangedWeapon {
+            ammo_type,
+            loaded,
+            reserve,
+            charge_level,
+        } = cond
+        {
+            // 检查条件只比较当前值
+            let new_only = |value: Option<ValueCmp>| {
+                value.map(|new| NewOldValueCmp {
+                    new: Some(new),
+                    old: None,
+                })
+            };
+            RangedWeaponCondition {
+                shared_ctx,
+                cond_ammo_type: ammo_type.map(AmmoCmp::parse),
+                cond_ammo_changed: None,
+                cond_loaded: new_only(loaded).map(LoadedCmp::Value),
+                cond_reserve: new_only(reserve),
+                cond_charge_level: new_only(charge_level),
+            }
+        } else {
+            error!("internal: RangedWeaponCondition cond 参数不正确");
+            panic!("internal: RangedWeaponCondition cond 参数不正确");
+        }
+    }
+
+    fn matches(&self, new: &RangedWeapon, old: &RangedWeapon, is_bow: bool) -> bool {
+        self.cond_ammo_type.as_ref().map_or(true, |cmp| cmp.matches(new.ammo_type, is_bow))
+            && self.cond_ammo_changed.map_or(true, |changed| changed == (new.ammo_type != old.ammo_type))
+            && self.cond_loaded.as_ref().map_or(true, |cmp| cmp.matches(new, old))
+            && compare_new_old(&self.cond_reserve, new.reserve, old.reserve)
+            && compare_new_old(&self.cond_charge_level, new.charge_level, old.charge_level)
+    }
+}
+
+async fn insert_ranged(action_ctx: &ActionContext, ctx: &Context) {
+    let ranged = &ctx.ranged;
+    let is_bow = WeaponType::Bow == ctx.weapon_type;
+    let ammo = game_context::find_ammo_by_id(ranged.ammo_type, is_bow)
+        .map_or_else(|| ranged.ammo_type.to_string(), |def| def.display_name.to_string());
+    let mut action_ctx = action_ctx.lock().await;
+    action_ctx.insert("ammo".to_string(), ammo);
+    action_ctx.insert("ammo_type".to_string(), ranged.ammo_type.to_string());
+    action_ctx.insert("loaded".to_string(), ranged.loaded.to_string());
+    action_ctx.insert("reserve".to_string(), ranged.reserve.to_string());
+    action_ctx.insert("charge_level".to_string(), ranged.charge_level.to_string());
+}
+
+#[async_trait]
+impl AsTriggerCondition for RangedWeaponCondition {
+    async fn check(&self, event: &Event, action_ctx: &ActionContext) -> bool {
+        if !matches!(event, Event::RangedWeapon) {
+            return false;
+        }
+        let ctx = self.shared_ctx.read().await;
+        let Some(last_ctx) = ctx.last_ctx.as_ref() else {
+            return false;
+        };
+        let is_bow = WeaponType::Bow == ctx.weapon_type;
+        if !self.matches(&ctx.ranged, &last_ctx.ranged, is_bow) {
+            return false;
+        }
+        insert_ranged(action_ctx, &ctx).await;
+        true
+    }
+
+    fn event_type(&self) -> EventType {
+        EventType::RangedWeapon
+    }
+}
+
+#[async_trait]
+impl AsCheckCondition for RangedWeaponCondition {
+    async fn check(&self, action_ctx: &ActionContext) -> bool {
+        let ctx = self.shared_ctx.read().await;
+        if !is_ranged_weapon(&ctx.weapon_type) {
+            return false;
+        }
+        let is_bow = WeaponType::Bow == ctx.weapon_type;
+        // 检查条件不比较旧值，直接使用当前值
+        if !self.matches(&ctx.ranged, &ctx.ranged, is_bow) {
+            return false;
+        }
+        insert_ranged(action_ctx, &ctx).await;
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ranged(ammo_type: i32, loaded: i32) -> RangedWeapon {
+        RangedWeapon {
+            ammo_type,
+            loaded,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_named_ammo() {
+        let normal_2 = AmmoCmp::parse(ValueCmp::Special("normal_2".to_string()));
+        assert!(normal_2.matches(1, false));
+        // 弓的强击瓶ID同样为 1
+        assert!(!normal_2.matches(1, true));
+
+        let power_coating = AmmoCmp::parse(ValueCmp::Special("power_coating".to_string()));
+        assert!(power_coating.matches(1, true));
+    }
+
+    #[test]
+    fn test_loaded_special() {
+        assert!(LoadedCmp::Empty.matches(&ranged(0, 0), &ranged(0, 1)));
+        assert!(!LoadedCmp::Empty.matches(&ranged(0, 1), &ranged(0, 2)));
+        // 切换到没有弹药的弹种
+        assert!(!LoadedCmp::Empty.matches(&ranged(3, 0), &ranged(0, 5)));
+
+        assert!(LoadedCmp::Reloaded.matches(&ranged(0, 6), &ranged(0, 0)));
+        assert!(!LoadedCmp::Reloaded.matches(&ranged(3, 5), &ranged(0, 0)));
+    }
+}
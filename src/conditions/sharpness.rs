@@ -0,0 +1,132 @@
+use async_trait::async_trait;
+use log::error;
+
+use crate::{
+    actions::ActionContext,
+    configs::{CheckCondition, NewOldValueCmp, TriggerCondition, ValueCmp},
+    event::{Event, EventType},
+    game_context::Sharpness,
+    triggers::{AsCheckCondition, AsTriggerCondition, SharedContext},
+};
+
+use super::compare_new_old;
+
+/// 锋利度条件
+pub struct SharpnessCondition {
+    shared_ctx: SharedContext,
+    cond_color: Option<NewOldValueCmp>,
+    cond_remaining: Option<NewOldValueCmp>,
+}
+
+impl SharpnessCondition {
+    pub fn new_trigger(cond: &TriggerCondition, shared_ctx: SharedContext) -> Self {
+        let cond = cond.clone();
+        if let TriggerCondition::SharpnessChanged { new, old, remaining } = cond {
+            SharpnessCondition {
+                shared_ctx,
+                cond_color: Some(NewOldValueCmp {
+                    new: parse_color(new),
+                    old: parse_color(old),
+                }),
+                cond_remaining: *remaining,
+            }
+        } else {
+            error!("internal: SharpnessCondition cond 参数不正确");
+            panic!("internal: SharpnessCondition cond 参数不正确");
+        }
+    }
+
+    pub fn new_check(cond: &CheckCondition, shared_ctx: SharedContext) -> Self {
+        let cond = cond.clone();
+        if let CheckCondition::Sharpness { color, remaining } = cond {
+            // 检查条件只比较当前值
+            let new_only = |value: Option<ValueCmp>| {
+                value.map(|new| NewOldValueCmp {
+                    new: Some(new),
+                    old: None,
+                })
+            };
+            SharpnessCondition {
+                shared_ctx,
+                cond_color: new_only(parse_color(color)),
+                cond_remaining: new_only(remaining),
+            }
+        } else {
+            error!("internal: SharpnessCondition cond 参数不正确");
+            panic!("internal: SharpnessCondition cond 参数不正确");
+        }
+    }
+
+    fn matches(&self, new: &Sharpness, old: &Sharpness) -> bool {
+        compare_new_old(&self.cond_color, new.color, old.color)
+            && compare_new_old(&self.cond_remaining, new.remaining, old.remaining)
+    }
+}
+
+/// 将颜色名称转换为颜色等级
+///
+/// 未知的颜色名称保留为特殊值，不会与任何等级匹配
+fn parse_color(value: Option<ValueCmp>) -> Option<ValueCmp> {
+    match value {
+        Some(ValueCmp::Special(name)) => match Sharpness::color_from_name(&name) {
+            Some(color) => Some(ValueCmp::EqInt(color)),
+            None => {
+                error!("锋利度颜色 {} 无定义，已拒绝条件", name);
+                Some(ValueCmp::Special(name))
+            }
+        },
+        other => other,
+    }
+}
+
+async fn insert_sharpness(action_ctx: &ActionContext, sharpness: &Sharpness) {
+    let mut action_ctx = action_ctx.lock().await;
+    action_ctx.insert("sharpness".to_string(), sharpness.color_display_name().to_string());
+    action_ctx.insert("sharpness_remaining".to_string(), sharpness.remaining.to_string());
+}
+
+#[async_trait]
+impl AsTriggerCondition for SharpnessCondition {
+    async fn check(&self, event: &Event, action_ctx: &ActionContext) -> bool {
+        if !matches!(event, Event::SharpnessChanged) {
+            return false;
+        }
+        let ctx = self.shared_ctx.read().await;
+        let Some(last_ctx) = ctx.last_ctx.as_ref() else {
+            return false;
+        };
+        if !self.matches(&ctx.sharpness, &last_ctx.sharpness) {
+            return false;
+        }
+        insert_sharpness(action_ctx, &ctx.sharpness).await;
+        true
+    }
+
+    fn event_type(&self) -> EventType {
+        EventType::SharpnessChanged
+    }
+}
+
+#[async_trait]
+impl AsCheckCondition for SharpnessCondition {
+    async fn check(&self, action_ctx: &ActionContext) -> bool {
+        let ctx = self.shared_ctx.read().await;
+        insert_sharpness(action_ctx, &ctx.sharpness).await;
+        self.matches(&ctx.sharpness, &ctx.sharpness)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_color() {
+        assert_eq!(parse_color(Some(ValueCmp::Special("white".to_string()))), Some(ValueCmp::EqInt(5)));
+        assert_eq!(parse_color(Some(ValueCmp::EqInt(3))), Some(ValueCmp::EqInt(3)));
+        assert_eq!(parse_color(None), None);
+        // 未知颜色不会匹配任何等级
+        let unknown = parse_color(Some(ValueCmp::Special("black".to_string()))).unwrap();
+        assert!((0..7).all(|color| unknown != color));
+    }
+}
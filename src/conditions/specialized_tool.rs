@@ -0,0 +1,130 @@
+use async_trait::async_trait;
+use log::error;
+
+use crate::{
+    actions::ActionContext,
+    configs::{CheckCondition, TriggerCondition, ValueCmp},
+    event::{Event, EventType},
+    game_context::Mantle,
+    triggers::{AsCheckCondition, AsTriggerCondition, SharedContext},
+};
+
+use super::TimerCmp;
+
+/// 衣装条件
+pub struct SpecializedToolCondition {
+    shared_ctx: SharedContext,
+    cond_tool_id: Option<ValueCmp>,
+    cond_active_timer: Option<TimerCmp>,
+    cond_cooldown_timer: Option<TimerCmp>,
+    cond_active: Option<bool>,
+    cond_ready: Option<bool>,
+}
+
+impl SpecializedToolCondition {
+    pub fn new_trigger(cond: &TriggerCondition, shared_ctx: SharedContext) -> Self {
+        let cond = cond.clone();
+        if let TriggerCondition::SpecializedTool {
+            tool_id,
+            active_timer,
+            cooldown_timer,
+        } = cond
+        {
+            SpecializedToolCondition {
+                shared_ctx,
+                cond_tool_id: tool_id,
+                cond_active_timer: TimerCmp::parse(*active_timer, Some("activated"), "expired"),
+                cond_cooldown_timer: TimerCmp::parse(*cooldown_timer, None, "ready"),
+                cond_active: None,
+                cond_ready: None,
+            }
+        } else {
+            error!("internal: SpecializedToolCondition cond 参数不正确");
+            panic!("internal: SpecializedToolCondition cond 参数不正确");
+        }
+    }
+
+    pub fn new_check(cond: &CheckCondition, shared_ctx: SharedContext) -> Self {
+        let cond = cond.clone();
+        if let CheckCondition::SpecializedTool { tool_id, active, ready } = cond {
+            SpecializedToolCondition {
+                shared_ctx,
+                cond_tool_id: tool_id,
+                cond_active_timer: None,
+                cond_cooldown_timer: None,
+                cond_active: active,
+                cond_ready: ready,
+            }
+        } else {
+            error!("internal: SpecializedToolCondition cond 参数不正确");
+            panic!("internal: SpecializedToolCondition cond 参数不正确");
+        }
+    }
+
+    fn matches_change(&self, new: &Mantle, old: &Mantle) -> bool {
+        new.is_equipped()
+            && new.id == old.id
+            && self.cond_tool_id.as_ref().map_or(true, |tool_id| *tool_id == new.id)
+            && self.cond_active_timer.as_ref().map_or(true, |cmp| cmp.matches(new.active_timer, old.active_timer))
+            && self
+                .cond_cooldown_timer
+                .as_ref()
+                .map_or(true, |cmp| cmp.matches(new.cooldown_timer, old.cooldown_timer))
+    }
+
+    fn matches_state(&self, mantle: &Mantle) -> bool {
+        mantle.is_equipped()
+            && self.cond_tool_id.as_ref().map_or(true, |tool_id| *tool_id == mantle.id)
+            && self.cond_active.map_or(true, |active| active == mantle.is_active())
+            && self.cond_ready.map_or(true, |ready| ready == mantle.is_ready())
+    }
+}
+
+async fn insert_mantle(action_ctx: &ActionContext, mantle: &Mantle) {
+    let mut action_ctx = action_ctx.lock().await;
+    action_ctx.insert("tool_id".to_string(), mantle.id.to_string());
+    action_ctx.insert("active_timer".to_string(), (mantle.active_timer.max(0.0) as i32).to_string());
+    action_ctx.insert("cooldown_timer".to_string(), (mantle.cooldown_timer.max(0.0) as i32).to_string());
+}
+
+#[async_trait]
+impl AsTriggerCondition for SpecializedToolCondition {
+    async fn check(&self, event: &Event, action_ctx: &ActionContext) -> bool {
+        if !matches!(event, Event::SpecializedTool) {
+            return false;
+        }
+        let ctx = self.shared_ctx.read().await;
+        let new = ctx.specialized_tool.as_ref();
+        let old = ctx.last_ctx.as_ref().and_then(|last_ctx| last_ctx.specialized_tool.as_ref());
+        let (Some(new), Some(old)) = (new, old) else {
+            return false;
+        };
+        let matched = new.slots.iter().zip(old.slots.iter()).find(|(new, old)| self.matches_change(new, old));
+        if let Some((mantle, _)) = matched {
+            insert_mantle(action_ctx, mantle).await;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn event_type(&self) -> EventType {
+        EventType::SpecializedTool
+    }
+}
+
+#[async_trait]
+impl AsCheckCondition for SpecializedToolCondition {
+    async fn check(&self, action_ctx: &ActionContext) -> bool {
+        let ctx = self.shared_ctx.read().await;
+        let Some(specialized_tool) = ctx.specialized_tool.as_ref() else {
+            return false;
+        };
+        if let Some(mantle) = specialized_tool.slots.iter().find(|mantle| self.matches_state(mantle)) {
+            insert_mantle(action_ctx, mantle).await;
+            true
+        } else {
+            false
+        }
+    }
+}
@@ -0,0 +1,105 @@
+use async_trait::async_trait;
+use log::error;
+
+use crate::{
+    actions::ActionContext,
+    configs::{CheckCondition, TriggerCondition},
+    event::{Event, EventType},
+    game_context,
+    triggers::{AsCheckCondition, AsTriggerCondition, SharedContext},
+};
+
+/// 增益、异常状态和猫饭技能条件
+pub struct StatusEffectCondition {
+    shared_ctx: SharedContext,
+    cond_name: Option<String>,
+    cond_active: Option<bool>,
+    cond_ailment: Option<bool>,
+    cond_food_skill: Option<bool>,
+}
+
+impl StatusEffectCondition {
+    pub fn new_trigger(cond: &TriggerCondition, shared_ctx: SharedContext) -> Self {
+        let cond = cond.clone();
+        if let TriggerCondition::StatusEffect {
+            name,
+            active,
+            ailment,
+            food_skill,
+        } = cond
+        {
+            StatusEffectCondition {
+                shared_ctx,
+                cond_name: name,
+                cond_active: active,
+                cond_ailment: ailment,
+                cond_food_skill: food_skill,
+            }
+        } else {
+            error!("internal: StatusEffectCondition cond 参数不正确");
+            panic!("internal: StatusEffectCondition cond 参数不正确");
+        }
+    }
+
+    pub fn new_check(cond: &CheckCondition, shared_ctx: SharedContext) -> Self {
+        let cond = cond.clone();
+        if let CheckCondition::StatusEffect { name, active } = cond {
+            StatusEffectCondition {
+                shared_ctx,
+                cond_name: Some(name),
+                cond_active: Some(active),
+                cond_ailment: None,
+                cond_food_skill: None,
+            }
+        } else {
+            error!("internal: StatusEffectCondition cond 参数不正确");
+            panic!("internal: StatusEffectCondition cond 参数不正确");
+        }
+    }
+
+    fn matches(&self, name: &str, active: bool) -> bool {
+        let (is_ailment, is_food_skill) = if let Some(def) = game_context::find_status_effect(name) {
+            (def.is_ailment, false)
+        } else if game_context::find_food_skill(name).is_some() {
+            (false, true)
+        } else {
+            return false;
+        };
+        self.cond_name.as_ref().map_or(true, |cond_name| cond_name == name)
+            && self.cond_active.map_or(true, |cond_active| cond_active == active)
+            && self.cond_ailment.map_or(true, |ailment| ailment == is_ailment)
+            && self.cond_food_skill.map_or(true, |food_skill| food_skill == is_food_skill)
+    }
+}
+
+#[async_trait]
+impl AsTriggerCondition for StatusEffectCondition {
+    async fn check(&self, event: &Event, action_ctx: &ActionContext) -> bool {
+        let Event::StatusEffectChanged { name, active } = event else {
+            return false;
+        };
+        if !self.matches(name, *active) {
+            return false;
+        }
+        let timer = self.shared_ctx.read().await.status_effects.get(name).copied().unwrap_or(0.0);
+        let mut action_ctx = action_ctx.lock().await;
+        action_ctx.insert("status".to_string(), name.clone());
+        action_ctx.insert("status_timer".to_string(), (timer.max(0.0) as i32).to_string());
+        true
+    }
+
+    fn event_type(&self) -> EventType {
+        EventType::StatusEffectChanged
+    }
+}
+
+#[async_trait]
+impl AsCheckCondition for StatusEffectCondition {
+    async fn check(&self, _action_ctx: &ActionContext) -> bool {
+        let Some(name) = &self.cond_name else {
+            return false;
+        };
+        let active = self.shared_ctx.read().await.is_status_active(name);
+        self.matches(name, active)
+    }
+}
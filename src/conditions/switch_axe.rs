@@ -0,0 +1,140 @@
+use async_trait::async_trait;
+use log::error;
+
+use crate::{
+    actions::ActionContext,
+    configs::{CheckCondition, NewOldValueCmp, TriggerCondition, ValueCmp},
+    event::{Event, EventType},
+    game_context::SwitchAxe,
+    triggers::{AsCheckCondition, AsTriggerCondition, SharedContext},
+};
+
+use super::{compare_new_old, TimerCmp};
+
+/// 斩击斧条件
+pub struct SwitchAxeCondition {
+    shared_ctx: SharedContext,
+    cond_switch_gauge: Option<NewOldValueCmp>,
+    cond_amp_gauge: Option<NewOldValueCmp>,
+    cond_amped_timer: Option<TimerCmp>,
+    cond_power_axe_timer: Option<TimerCmp>,
+    cond_amped: Option<bool>,
+    cond_power_axe: Option<bool>,
+}
+
+impl SwitchAxeCondition {
+    pub fn new_trigger(cond: &TriggerCondition, shared_ctx: SharedContext) -> Self {
+        let cond = cond.clone();
+        if let TriggerCondition::SwitchAxe {
+            switch_gauge,
+            amp_gauge,
+            amped_timer,
+            power_axe_timer,
+        } = cond
+        {
+            SwitchAxeCondition {
+                shared_ctx,
+                cond_switch_gauge: *switch_gauge,
+                cond_amp_gauge: *amp_gauge,
+                cond_amped_timer: TimerCmp::parse(*amped_timer, Some("enabled"), "disabled"),
+                cond_power_axe_timer: TimerCmp::parse(*power_axe_timer, Some("enabled"), "disabled"),
+                cond_amped: None,
+                cond_power_axe: None,
+            }
+        } else {
+            error!("internal: SwitchAxeCondition cond 参数不正确");
+            panic!("internal: SwitchAxeCondition cond 参数不正确");
+        }
+    }
+
+    pub fn new_check(cond: &CheckCondition, shared_ctx: SharedContext) -> Self {
+        let cond = cond.clone();
+        if let CheckCondition::SwitchAxe {
+            switch_gauge,
+            amp_gauge,
+            amped,
+            power_axe,
+        } = cond
+        {
+            SwitchAxeCondition {
+                shared_ctx,
+                // 检查条件只比较当前值
+                cond_switch_gauge: switch_gauge.map(current_value_cmp),
+                cond_amp_gauge: amp_gauge.map(current_value_cmp),
+                cond_amped_timer: None,
+                cond_power_axe_timer: None,
+                cond_amped: amped,
+                cond_power_axe: power_axe,
+            }
+        } else {
+            error!("internal: SwitchAxeCondition cond 参数不正确");
+            panic!("internal: SwitchAxeCondition cond 参数不正确");
+        }
+    }
+
+    fn matches_change(&self, new: &SwitchAxe, old: &SwitchAxe) -> bool {
+        compare_new_old(&self.cond_switch_gauge, new.switch_gauge as i32, old.switch_gauge as i32)
+            && compare_new_old(&self.cond_amp_gauge, new.amp_gauge as i32, old.amp_gauge as i32)
+            && self.cond_amped_timer.as_ref().map_or(true, |cmp| cmp.matches(new.amped_timer, old.amped_timer))
+            && self
+                .cond_power_axe_timer
+                .as_ref()
+                .map_or(true, |cmp| cmp.matches(new.power_axe_timer, old.power_axe_timer))
+    }
+
+    fn matches_state(&self, switch_axe: &SwitchAxe) -> bool {
+        compare_new_old(&self.cond_switch_gauge, switch_axe.switch_gauge as i32, 0)
+            && compare_new_old(&self.cond_amp_gauge, switch_axe.amp_gauge as i32, 0)
+            && self.cond_amped.map_or(true, |amped| amped == switch_axe.is_amped())
+            && self.cond_power_axe.map_or(true, |power_axe| power_axe == switch_axe.is_power_axe())
+    }
+}
+
+fn current_value_cmp(value: ValueCmp) -> NewOldValueCmp {
+    NewOldValueCmp {
+        new: Some(value),
+        old: None,
+    }
+}
+
+async fn insert_switch_axe(action_ctx: &ActionContext, switch_axe: &SwitchAxe) {
+    let mut action_ctx = action_ctx.lock().await;
+    action_ctx.insert("switch_gauge".to_string(), (switch_axe.switch_gauge as i32).to_string());
+    action_ctx.insert("amp_gauge".to_string(), (switch_axe.amp_gauge as i32).to_string());
+    action_ctx.insert("amped_timer".to_string(), (switch_axe.amped_timer.max(0.0) as i32).to_string());
+    action_ctx.insert("power_axe_timer".to_string(), (switch_axe.power_axe_timer.max(0.0) as i32).to_string());
+}
+
+#[async_trait]
+impl AsTriggerCondition for SwitchAxeCondition {
+    async fn check(&self, event: &Event, action_ctx: &ActionContext) -> bool {
+        if !matches!(event, Event::SwitchAxe) {
+            return false;
+        }
+        let ctx = self.shared_ctx.read().await;
+        let Some(last_ctx) = ctx.last_ctx.as_ref() else {
+            return false;
+        };
+        if !self.matches_change(&ctx.switch_axe, &last_ctx.switch_axe) {
+            return false;
+        }
+        insert_switch_axe(action_ctx, &ctx.switch_axe).await;
+        true
+    }
+
+    fn event_type(&self) -> EventType {
+        EventType::SwitchAxe
+    }
+}
+
+#[async_trait]
+impl AsCheckCondition for SwitchAxeCondition {
+    async fn check(&self, action_ctx: &ActionContext) -> bool {
+        let ctx = self.shared_ctx.read().await;
+        if ctx.weapon_type != 8 || !self.matches_state(&ctx.switch_axe) {
+            return false;
+        }
+        insert_switch_axe(action_ctx, &ctx.switch_axe).await;
+        true
+    }
+}
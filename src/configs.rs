@@ -1,4 +1,4 @@
-use std::{fs, path::Path};
+use std::{collections::HashMap, fs, path::Path, time::Duration};
 
 use serde::{Deserialize, Serialize};
 use snafu::prelude::*;
@@ -25,14 +25,96 @@ pub struct Config {
     /// 可被触发器设置覆盖
     #[serde(default = "default_event_cd")]
     pub trigger_cd: f32,
+    /// 冷却组冷却时间（秒）
+    ///
+    /// 同一冷却组内的触发器共享冷却时间
+    ///
+    /// 未在此设置的冷却组使用全局冷却时间
+    #[serde(default)]
+    pub cooldown_groups: HashMap<String, f32>,
+    /// 聊天消息发送队列设置
+    #[serde(default)]
+    pub chat_queue: ChatQueueConfig,
+    /// 伤害记录设置
+    #[serde(default)]
+    pub damage: DamageConfig,
     #[serde(default)]
     pub trigger: Vec<Trigger>,
+    /// 动作名称：可选，用于伤害总结等统计中显示动作名称
+    #[serde(default)]
+    pub move_name: Vec<MoveName>,
 }
 
 fn default_event_cd() -> f32 {
     0.5
 }
 
+/// 聊天消息发送队列设置
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ChatQueueConfig {
+    /// 最小发送间隔（秒）
+    ///
+    /// 连续发送不超过 `max_burst` 条消息时不受此间隔限制
+    #[serde(default = "default_chat_min_interval")]
+    pub min_interval: f32,
+    /// 最多连续发送的消息数量
+    #[serde(default = "default_chat_max_burst")]
+    pub max_burst: u32,
+}
+
+impl Default for ChatQueueConfig {
+    fn default() -> Self {
+        Self {
+            min_interval: default_chat_min_interval(),
+            max_burst: default_chat_max_burst(),
+        }
+    }
+}
+
+fn default_chat_min_interval() -> f32 {
+    0.5
+}
+
+fn default_chat_max_burst() -> u32 {
+    3
+}
+
+/// 动作名称
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct MoveName {
+    pub target: i32,
+    pub id: i32,
+    pub name: String,
+}
+
+/// 伤害记录设置
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct DamageConfig {
+    /// 伤害记录保留时间（秒）
+    #[serde(default = "default_damage_retention")]
+    pub retention: f32,
+    /// 最多保留的伤害记录数量
+    #[serde(default = "default_damage_max_records")]
+    pub max_records: usize,
+}
+
+impl Default for DamageConfig {
+    fn default() -> Self {
+        Self {
+            retention: default_damage_retention(),
+            max_records: default_damage_max_records(),
+        }
+    }
+}
+
+fn default_damage_retention() -> f32 {
+    120.0
+}
+
+fn default_damage_max_records() -> usize {
+    4096
+}
+
 /// 触发器
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Trigger {
@@ -50,8 +132,19 @@ pub struct Trigger {
     /// 冷却时间（秒）
     /// 覆盖全局设置
     pub cooldown: Option<f32>,
+    /// 冷却组：同组触发器共享冷却时间
+    /// 与触发器自身冷却时间同时生效
+    ///
+    /// 冷却时间在全局配置 `cooldown_groups` 中设置，未设置时使用全局冷却时间 `trigger_cd`
+    pub cooldown_group: Option<String>,
     /// 记录触发次数
     pub enable_cnt: Option<bool>,
+    /// 单次任务内最多触发次数
+    /// 回到据点时重置
+    pub max_fires_per_quest: Option<i32>,
+    /// 本次游戏内最多触发次数
+    /// 重载配置后重置
+    pub max_fires_per_session: Option<i32>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -60,6 +153,10 @@ pub struct Action {
     pub cmd: Command,
     /// 参数
     pub param: String,
+    /// 发送优先级：可选，越大越优先，默认为0
+    pub priority: Option<i32>,
+    /// 丢弃时间（秒）：可选，消息在发送队列中等待超过该时间后丢弃
+    pub drop_after: Option<f32>,
 }
 
 /// 触发器条件
@@ -74,6 +171,13 @@ pub enum TriggerCondition {
         new: Option<ValueCmp>,
         old: Option<ValueCmp>,
     },
+    /// 太刀气刃槽和开刃等级时间变化
+    LongswordSpirit {
+        /// 气刃槽（0-100），`new` 可使用特殊值 "full"（气刃槽已满）
+        gauge: Box<Option<NewOldValueCmp>>,
+        /// 当前开刃等级剩余时间（秒）
+        level_timer: Box<Option<NewOldValueCmp>>,
+    },
     WeaponType {
         value: ValueCmp,
     },
@@ -99,6 +203,141 @@ pub enum TriggerCondition {
         phials: Box<Option<NewOldValueCmp>>,
         sword_power: Box<Option<NewOldValueCmp>>,
     },
+    /// 斩击斧状态变化
+    SwitchAxe {
+        /// 剑槽（0-100）
+        switch_gauge: Box<Option<NewOldValueCmp>>,
+        /// 觉醒槽（0-100）
+        amp_gauge: Box<Option<NewOldValueCmp>>,
+        /// 剑强化剩余时间（秒），`new` 可使用特殊值 "enabled"（开始）和 "disabled"（结束）
+        amped_timer: Box<Option<NewOldValueCmp>>,
+        /// 斧强化剩余时间（秒），`new` 可使用特殊值 "enabled"（开始）和 "disabled"（结束）
+        power_axe_timer: Box<Option<NewOldValueCmp>>,
+    },
+    /// 铳枪状态变化
+    Gunlance {
+        /// 弹药数量，`new` 可使用特殊值 "full"（装满）和 "empty"（打空）
+        shells: Box<Option<NewOldValueCmp>>,
+        /// 龙杭弹装填状态，`true` 为装填，`false` 为打出
+        stake_loaded: Option<bool>,
+        /// 龙击炮冷却时间（秒），`new` 可使用特殊值 "ready"（冷却完毕）
+        wyvernfire_cooldown: Box<Option<NewOldValueCmp>>,
+        /// 龙杭炮装填状态，`true` 为装填，`false` 为打出
+        wyrmstake_loaded: Option<bool>,
+    },
+    /// 狩猎笛演奏旋律
+    HuntingHornSong {
+        /// 旋律ID，可使用旋律名称，例如 "attack_up"
+        song: Option<ValueCmp>,
+    },
+    /// 狩猎笛旋律槽变化
+    HuntingHornQueue {
+        /// 旋律槽中的旋律数量，`new` 可使用特殊值 "full"（旋律槽已满）
+        queue_size: Box<Option<NewOldValueCmp>>,
+    },
+    /// 弓和弩的弹药、瓶或蓄力变化
+    RangedWeapon {
+        /// 变化后的弹药类型ID，可使用弹药名称，例如 "normal_2"、"power_coating"
+        ammo_type: Option<ValueCmp>,
+        /// `true` 时仅在切换弹药或瓶时触发
+        ammo_changed: Option<bool>,
+        /// 已装填的弹数（弓为瓶的剩余数量），`new` 可使用特殊值 "empty"（打空）和 "reloaded"（装填）
+        loaded: Box<Option<NewOldValueCmp>>,
+        /// 当前弹药的剩余携带数量
+        reserve: Box<Option<NewOldValueCmp>>,
+        /// 弓的蓄力等级
+        charge_level: Box<Option<NewOldValueCmp>>,
+    },
+    /// 双刀鬼人化状态变化
+    DualBlades {
+        /// `true` 在进入鬼人化时触发，`false` 在解除时触发
+        demon_mode: Option<bool>,
+        /// 鬼人槽（0-100）
+        archdemon_gauge: Box<Option<NewOldValueCmp>>,
+    },
+    /// 大剑和大锤蓄力等级变化
+    ChargeLevel {
+        new: Option<ValueCmp>,
+        old: Option<ValueCmp>,
+        /// `true` 时仅在蓄力结束（等级归零）时触发，此时 `old` 为释放时的蓄力等级
+        released: Option<bool>,
+    },
+    /// 任务伤害总结：任务完成时触发
+    QuestSummary {
+        total_damage: Option<ValueCmp>,
+    },
+    /// 单次命中伤害：每次造成伤害时判断
+    Damage {
+        value: Option<ValueCmp>,
+        crit: Option<bool>,
+        tenderized: Option<bool>,
+        /// 命中冷却时间（秒）
+        /// 满足条件的命中在冷却时间内只判断一次，用于合并多段攻击
+        cooldown: Option<f32>,
+    },
+    /// 怪物出现或消失（需要启用钩子功能）
+    Monster {
+        event: MonsterEvent,
+        /// 怪物ID
+        monster_id: Option<ValueCmp>,
+    },
+    /// 怪物状态变化
+    ///
+    /// 布尔值条件为 `true` 时在进入该状态时触发，为 `false` 时在离开该状态时触发
+    MonsterState {
+        /// 怪物ID
+        monster_id: Option<ValueCmp>,
+        /// 体力百分比
+        health: Box<Option<NewOldValueCmp>>,
+        /// 愤怒
+        enraged: Option<bool>,
+        /// 疲劳
+        exhausted: Option<bool>,
+        /// 被陷阱困住
+        trapped: Option<bool>,
+    },
+    /// 玩家猫车
+    PlayerFainted {
+        /// 本次任务的猫车次数
+        faint_count: Option<ValueCmp>,
+    },
+    /// 玩家体力和耐力变化（仅在任务中）
+    PlayerVitals {
+        /// 体力百分比
+        health: Box<Option<NewOldValueCmp>>,
+        /// 单次变化损失的体力，占最大体力的百分比
+        health_lost: Option<ValueCmp>,
+        /// 耐力
+        stamina: Box<Option<NewOldValueCmp>>,
+    },
+    /// 衣装状态变化：任意一件装备的衣装满足所有条件即可
+    SpecializedTool {
+        /// 衣装ID
+        tool_id: Option<ValueCmp>,
+        /// 剩余效果时间（秒），`new` 可使用特殊值 "activated"（开始生效）和 "expired"（效果结束）
+        active_timer: Box<Option<NewOldValueCmp>>,
+        /// 剩余冷却时间（秒），`new` 可使用特殊值 "ready"（冷却完毕）
+        cooldown_timer: Box<Option<NewOldValueCmp>>,
+    },
+    /// 增益、异常状态或猫饭技能开始/结束
+    StatusEffect {
+        /// 状态名称，不设置时判断所有状态
+        name: Option<String>,
+        /// `true` 在开始生效时触发，`false` 在结束时触发，不设置时均触发
+        active: Option<bool>,
+        /// 仅判断异常状态（true）或非异常状态（false）
+        ailment: Option<bool>,
+        /// 仅判断猫饭技能（true）或增益和异常状态（false）
+        food_skill: Option<bool>,
+    },
+    /// 锋利度变化
+    SharpnessChanged {
+        /// 颜色等级，可使用颜色名称，例如 "white"
+        new: Option<ValueCmp>,
+        old: Option<ValueCmp>,
+        /// 当前颜色剩余锋利度
+        remaining: Box<Option<NewOldValueCmp>>,
+    },
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -116,6 +355,13 @@ pub enum CheckCondition {
     LongswordLevel {
         value: ValueCmp,
     },
+    /// 太刀气刃槽和开刃等级时间
+    LongswordSpirit {
+        /// 气刃槽（0-100）
+        gauge: Option<ValueCmp>,
+        /// 当前开刃等级剩余时间（秒）
+        level_timer: Option<ValueCmp>,
+    },
     WeaponType {
         value: ValueCmp,
     },
@@ -125,12 +371,137 @@ pub enum CheckCondition {
     Fsm {
         value: FsmConfig,
     },
+    /// 斩击斧状态
+    SwitchAxe {
+        switch_gauge: Option<ValueCmp>,
+        amp_gauge: Option<ValueCmp>,
+        /// 是否剑强化
+        amped: Option<bool>,
+        /// 是否斧强化
+        power_axe: Option<bool>,
+    },
+    /// 铳枪状态
+    Gunlance {
+        /// 弹药数量，可使用特殊值 "full" 和 "empty"
+        shells: Option<ValueCmp>,
+        /// 是否装填龙杭弹
+        stake_loaded: Option<bool>,
+        /// 龙击炮是否可用
+        wyvernfire_ready: Option<bool>,
+        /// 是否装填龙杭炮
+        wyrmstake_loaded: Option<bool>,
+    },
+    /// 狩猎笛旋律槽
+    HuntingHorn {
+        /// 旋律槽中是否有指定旋律，可使用旋律名称
+        queued_song: Option<ValueCmp>,
+        /// 旋律槽中的旋律数量
+        queue_size: Option<ValueCmp>,
+    },
+    /// 弓和弩的弹药、瓶和蓄力
+    RangedWeapon {
+        /// 当前弹药类型ID，可使用弹药名称
+        ammo_type: Option<ValueCmp>,
+        /// 已装填的弹数（弓为瓶的剩余数量）
+        loaded: Option<ValueCmp>,
+        /// 当前弹药的剩余携带数量
+        reserve: Option<ValueCmp>,
+        /// 弓的蓄力等级
+        charge_level: Option<ValueCmp>,
+    },
+    /// 双刀鬼人化状态
+    DualBlades {
+        /// 是否处于鬼人化
+        demon_mode: Option<bool>,
+        /// 鬼人槽（0-100）
+        archdemon_gauge: Option<ValueCmp>,
+    },
+    /// 大剑和大锤蓄力等级
+    ChargeLevel {
+        value: ValueCmp,
+    },
     Damage {
-        damage: ValueCmp,
-        fsm: FsmConfig,
+        /// 总伤害
+        damage: Option<ValueCmp>,
+        /// 命中次数
+        hits: Option<ValueCmp>,
+        /// 会心次数
+        crits: Option<ValueCmp>,
+        /// 单次最高伤害
+        max_hit: Option<ValueCmp>,
+        /// 单次最低伤害
+        min_hit: Option<ValueCmp>,
+        /// 平均单次伤害
+        avg_hit: Option<ValueCmp>,
+        /// 动作：可选，单个或多个
+        /// 设置时仅在当前动作满足时检查，且只统计这些动作造成的伤害
+        fsm: Option<FsmList>,
         timeout: Option<i32>,
         #[serde(default)]
         break_on_fsm_changed: bool,
+        /// 仅统计会心（或非会心）命中
+        crit: Option<bool>,
+        /// 仅统计命中伤口（或未命中伤口）的攻击
+        tenderized: Option<bool>,
+        /// 仅统计与首次命中相同的怪物
+        #[serde(default)]
+        same_monster: bool,
+    },
+    /// 计数：统计触发条件被满足的次数
+    ///
+    /// 设置 `window_ms` 时只统计该时间窗口（毫秒）内的次数，否则统计本次任务内的次数
+    Count {
+        value: ValueCmp,
+        window_ms: Option<i64>,
+    },
+    /// 个人最佳记录：需要放在 damage 检查条件之后
+    ///
+    /// 默认按动作记录，可通过 `key` 自定义记录名称
+    PersonalBest {
+        is_pb: Option<bool>,
+        key: Option<String>,
+    },
+    /// 怪物状态：任意一只追踪的怪物满足所有条件即可
+    MonsterState {
+        monster_id: Option<ValueCmp>,
+        /// 体力百分比
+        health: Option<ValueCmp>,
+        enraged: Option<bool>,
+        exhausted: Option<bool>,
+        trapped: Option<bool>,
+    },
+    /// 本次任务的猫车次数
+    Faints {
+        value: ValueCmp,
+    },
+    /// 玩家体力和耐力
+    PlayerVitals {
+        /// 体力百分比
+        health: Option<ValueCmp>,
+        /// 可恢复体力（红血）
+        red_health: Option<ValueCmp>,
+        /// 耐力
+        stamina: Option<ValueCmp>,
+    },
+    /// 衣装状态：任意一件装备的衣装满足所有条件即可
+    SpecializedTool {
+        tool_id: Option<ValueCmp>,
+        /// 是否生效中
+        active: Option<bool>,
+        /// 是否可以使用
+        ready: Option<bool>,
+    },
+    /// 增益、异常状态或猫饭技能是否生效中
+    StatusEffect {
+        name: String,
+        active: bool,
+    },
+    /// 锋利度
+    Sharpness {
+        /// 颜色等级，可使用颜色名称
+        color: Option<ValueCmp>,
+        /// 当前颜色剩余锋利度
+        remaining: Option<ValueCmp>,
     },
 }
 
@@ -146,6 +517,24 @@ impl PartialEq<game_context::Fsm> for FsmConfig {
     }
 }
 
+/// 单个或多个动作
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum FsmList {
+    One(FsmConfig),
+    Many(Vec<FsmConfig>),
+}
+
+impl FsmList {
+    /// 是否包含某个动作
+    pub fn contains(&self, fsm: &game_context::Fsm) -> bool {
+        match self {
+            FsmList::One(cfg) => cfg == fsm,
+            FsmList::Many(cfgs) => cfgs.iter().any(|cfg| cfg == fsm),
+        }
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, PartialOrd)]
 #[serde(untagged)]
 pub enum ValueCmp {
@@ -198,6 +587,16 @@ pub enum Command {
     SendChatMessage,
 }
 
+/// 怪物事件类型
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum MonsterEvent {
+    /// 怪物出现
+    Spawned,
+    /// 怪物消失
+    Despawned,
+}
+
 /// 触发器行为模式
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -230,9 +629,62 @@ where
             reason: "event_cd 不能小于0.0".to_string(),
         });
     }
+    // 排除负数、NaN、inf 和超出范围的值
+    if Duration::try_from_secs_f32(config.chat_queue.min_interval).is_err() {
+        return Err(ConfigError::Validate {
+            reason: "chat_queue.min_interval 必须为有效的非负数".to_string(),
+        });
+    }
+    if config.chat_queue.max_burst == 0 {
+        return Err(ConfigError::Validate {
+            reason: "chat_queue.max_burst 不能为0".to_string(),
+        });
+    }
+    if config.damage.retention < 0.0 {
+        return Err(ConfigError::Validate {
+            reason: "damage.retention 不能小于0.0".to_string(),
+        });
+    }
+    if config.damage.max_records == 0 {
+        return Err(ConfigError::Validate {
+            reason: "damage.max_records 不能为0".to_string(),
+        });
+    }
+    if let Some((name, _)) = config.cooldown_groups.iter().find(|(_, cd)| **cd < 0.0) {
+        return Err(ConfigError::Validate {
+            reason: format!("冷却组 {} 的冷却时间不能小于0.0", name),
+        });
+    }
     for t in config.trigger.iter_mut() {
         // 为Trigger应用全局默认设置
         t.cooldown = Some(t.cooldown.unwrap_or(config.trigger_cd));
+        // 检查行为设置
+        if t.action.iter().any(|a| a.drop_after.is_some_and(|v| Duration::try_from_secs_f32(v).is_err())) {
+            return Err(ConfigError::Validate {
+                reason: "drop_after 必须为有效的非负数".to_string(),
+            });
+        }
+        // 检查触发次数限制
+        if t.max_fires_per_quest.is_some_and(|v| v < 0) || t.max_fires_per_session.is_some_and(|v| v < 0) {
+            return Err(ConfigError::Validate {
+                reason: "max_fires_per_quest 和 max_fires_per_session 不能小于0".to_string(),
+            });
+        }
+        // 检查计数时间窗口
+        for c in t.check.iter() {
+            if let CheckCondition::Count { window_ms: Some(w), .. } = c {
+                if *w <= 0 {
+                    return Err(ConfigError::Validate {
+                        reason: "Count window_ms 必须大于0".to_string(),
+                    });
+                }
+                if chrono::Duration::try_milliseconds(*w).is_none() {
+                    return Err(ConfigError::Validate {
+                        reason: "Count window_ms 超出范围".to_string(),
+                    });
+                }
+            }
+        }
         // 检查触发器条件
         if let TriggerCondition::LongswordLevelChanged { new, old } = &t.trigger_on {
             if new.is_none() && old.is_none() {
@@ -241,6 +693,90 @@ where
                 });
             }
         }
+        if let TriggerCondition::Damage { cooldown: Some(cd), .. } = &t.trigger_on {
+            if *cd < 0.0 {
+                return Err(ConfigError::Validate {
+                    reason: "Damage cooldown 不能小于0.0".to_string(),
+                });
+            }
+        }
+        // 检查状态名称
+        let status_names = t.check.iter().filter_map(|c| match c {
+            CheckCondition::StatusEffect { name, .. } => Some(name),
+            _ => None,
+        });
+        let status_names = status_names.chain(match &t.trigger_on {
+            TriggerCondition::StatusEffect { name: Some(name), .. } => Some(name),
+            _ => None,
+        });
+        for name in status_names {
+            if game_context::find_status_effect(name).is_none() && game_context::find_food_skill(name).is_none() {
+                return Err(ConfigError::Validate {
+                    reason: format!("未知的状态名称 {}", name),
+                });
+            }
+        }
+        // 检查旋律名称
+        let song_names = t.check.iter().filter_map(|c| match c {
+            CheckCondition::HuntingHorn { queued_song, .. } => queued_song.as_ref(),
+            _ => None,
+        });
+        let song_names = song_names.chain(match &t.trigger_on {
+            TriggerCondition::HuntingHornSong { song } => song.as_ref(),
+            _ => None,
+        });
+        for song in song_names {
+            if let ValueCmp::Special(name) = song {
+                if game_context::find_song(name).is_none() {
+                    return Err(ConfigError::Validate {
+                        reason: format!("未知的旋律名称 {}", name),
+                    });
+                }
+            }
+        }
+        // 检查弹药名称
+        let ammo_types = t.check.iter().filter_map(|c| match c {
+            CheckCondition::RangedWeapon { ammo_type, .. } => ammo_type.as_ref(),
+            _ => None,
+        });
+        let ammo_types = ammo_types.chain(match &t.trigger_on {
+            TriggerCondition::RangedWeapon { ammo_type, .. } => ammo_type.as_ref(),
+            _ => None,
+        });
+        for ammo_type in ammo_types {
+            if let ValueCmp::Special(name) = ammo_type {
+                if game_context::find_ammo(name).is_none() {
+                    return Err(ConfigError::Validate {
+                        reason: format!("未知的弹药名称 {}", name),
+                    });
+                }
+            }
+        }
+        // 检查装填数量特殊值
+        if let TriggerCondition::RangedWeapon { loaded, .. } = &t.trigger_on {
+            if let Some(NewOldValueCmp {
+                new: Some(ValueCmp::Special(s)),
+                ..
+            }) = loaded.as_ref()
+            {
+                if s != "empty" && s != "reloaded" {
+                    return Err(ConfigError::Validate {
+                        reason: format!("loaded 不支持值 {}", s),
+                    });
+                }
+            }
+        }
+        for c in t.check.iter() {
+            if let CheckCondition::RangedWeapon {
+                loaded: Some(ValueCmp::Special(s)),
+                ..
+            } = c
+            {
+                return Err(ConfigError::Validate {
+                    reason: format!("检查条件 loaded 不支持值 {}", s),
+                });
+            }
+        }
     }
 
     Ok(config)
@@ -0,0 +1,150 @@
+use std::collections::VecDeque;
+
+use chrono::{DateTime, TimeDelta, Utc};
+
+use crate::game_context::Fsm;
+
+/// 单次伤害记录
+#[derive(Clone, Debug)]
+pub struct DamageData {
+    pub damage: i32,
+    pub fsm: Fsm,
+    pub time: DateTime<Utc>,
+    /// 是否会心
+    pub is_crit: bool,
+    /// 是否命中伤口
+    pub is_tenderized: bool,
+    /// 受击目标地址
+    pub target: usize,
+}
+
+/// 伤害记录账本
+///
+/// 按时间顺序保存伤害记录，超出保留时间或最大记录数的旧记录会被移除，
+/// 时间区间查询使用二分搜索
+pub struct DamageLedger {
+    records: VecDeque<DamageData>,
+    /// 保留时间
+    retention: TimeDelta,
+    /// 最大记录数
+    max_records: usize,
+}
+
+impl DamageLedger {
+    pub fn new(retention: TimeDelta, max_records: usize) -> Self {
+        DamageLedger {
+            records: VecDeque::new(),
+            retention,
+            max_records: max_records.max(1),
+        }
+    }
+
+    /// 修改保留时间和最大记录数
+    pub fn set_limits(&mut self, retention: TimeDelta, max_records: usize) {
+        self.retention = retention;
+        self.max_records = max_records.max(1);
+        self.truncate();
+        self.clear_expired(Utc::now());
+    }
+
+    /// 添加记录
+    ///
+    /// 记录需按时间顺序添加
+    pub fn push(&mut self, data: DamageData) {
+        let now = data.time;
+        self.records.push_back(data);
+        self.truncate();
+        self.clear_expired(now);
+    }
+
+    /// 清除超出保留时间的记录
+    pub fn clear_expired(&mut self, now: DateTime<Utc>) {
+        let expire_before = now - self.retention;
+        let cut_index = self.records.partition_point(|data| data.time < expire_before);
+        self.records.drain(..cut_index);
+    }
+
+    /// 获取时间区间 `[start_time, end_time]` 内的记录
+    pub fn range(&self, start_time: DateTime<Utc>, end_time: DateTime<Utc>) -> impl Iterator<Item = &DamageData> {
+        let start_idx = self.records.partition_point(|data| data.time < start_time);
+        let end_idx = self.records.partition_point(|data| data.time <= end_time).max(start_idx);
+        self.records.range(start_idx..end_idx)
+    }
+
+    #[cfg(test)]
+    fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    fn truncate(&mut self) {
+        if self.records.len() > self.max_records {
+            let cut_count = self.records.len() - self.max_records;
+            self.records.drain(..cut_count);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn damage_data(damage: i32, time: DateTime<Utc>) -> DamageData {
+        DamageData {
+            damage,
+            fsm: Fsm::default(),
+            time,
+            is_crit: false,
+            is_tenderized: false,
+            target: 0,
+        }
+    }
+
+    #[test]
+    fn test_ledger_bounded_by_max_records() {
+        let start = Utc::now();
+        let mut ledger = DamageLedger::new(TimeDelta::try_hours(1).unwrap(), 1000);
+        for i in 0..100_000 {
+            ledger.push(damage_data(i, start + TimeDelta::try_milliseconds(i as i64).unwrap()));
+            assert!(ledger.len() <= 1000);
+        }
+        assert_eq!(ledger.len(), 1000);
+    }
+
+    #[test]
+    fn test_ledger_bounded_by_retention() {
+        let start = Utc::now();
+        let mut ledger = DamageLedger::new(TimeDelta::try_seconds(1).unwrap(), usize::MAX);
+        // 每10毫秒一次伤害，1秒内最多保留101条
+        for i in 0..100_000 {
+            ledger.push(damage_data(i, start + TimeDelta::try_milliseconds(i as i64 * 10).unwrap()));
+            assert!(ledger.len() <= 101);
+        }
+    }
+
+    #[test]
+    fn test_ledger_range() {
+        let start = Utc::now();
+        let mut ledger = DamageLedger::new(TimeDelta::try_hours(1).unwrap(), 1000);
+        for i in 0..10 {
+            ledger.push(damage_data(i, start + TimeDelta::try_seconds(i as i64).unwrap()));
+        }
+        let range_start = start + TimeDelta::try_seconds(2).unwrap();
+        let range_end = start + TimeDelta::try_seconds(5).unwrap();
+        let damages: Vec<i32> = ledger.range(range_start, range_end).map(|data| data.damage).collect();
+        assert_eq!(damages, vec![2, 3, 4, 5]);
+
+        assert_eq!(ledger.range(range_end, range_start).count(), 0);
+    }
+
+    #[test]
+    fn test_ledger_set_limits() {
+        let start = Utc::now();
+        let mut ledger = DamageLedger::new(TimeDelta::try_hours(1).unwrap(), 1000);
+        for i in 0..100 {
+            ledger.push(damage_data(i, start + TimeDelta::try_milliseconds(i as i64).unwrap()));
+        }
+        ledger.set_limits(TimeDelta::try_hours(1).unwrap(), 10);
+        assert_eq!(ledger.len(), 10);
+        assert_eq!(ledger.range(start, start + TimeDelta::try_hours(1).unwrap()).next().unwrap().damage, 90);
+    }
+}
@@ -1,7 +1,7 @@
 use mhw_toolkit::game::resources::WeaponType;
 
 use crate::{
-    game_context::{Context, Fsm},
+    game_context::{Context, Fsm, Longsword},
     triggers::TriggerManager,
 };
 
@@ -9,16 +9,86 @@ use crate::{
 #[derive(Debug, Clone)]
 #[allow(dead_code)]
 pub enum Event {
-    LoadTriggers { trigger_mgr: TriggerManager },
-    UpdateContext { ctx: Context },
-    LongswordLevelChanged { new: i32, old: i32 },
-    WeaponTypeChanged { new: WeaponType, old: WeaponType },
-    QuestStateChanged { new: i32, old: i32 },
-    FsmChanged { new: Fsm, old: Fsm },
-    UseItem { item_id: i32 },
+    LoadTriggers {
+        trigger_mgr: TriggerManager,
+    },
+    UpdateContext {
+        ctx: Context,
+    },
+    LongswordLevelChanged {
+        new: i32,
+        old: i32,
+    },
+    LongswordSpiritChanged {
+        new: Longsword,
+        old: Longsword,
+    },
+    WeaponTypeChanged {
+        new: WeaponType,
+        old: WeaponType,
+    },
+    QuestStateChanged {
+        new: i32,
+        old: i32,
+    },
+    FsmChanged {
+        new: Fsm,
+        old: Fsm,
+    },
+    UseItem {
+        item_id: i32,
+    },
     InsectGlaive,
     ChargeBlade,
-    Damage { damage: i32 },
+    SwitchAxe,
+    Gunlance,
+    HuntingHornSongPerformed {
+        /// 旋律ID
+        song_id: i32,
+    },
+    HuntingHornQueueChanged,
+    RangedWeapon,
+    DualBlades,
+    ChargeLevelChanged {
+        new: i32,
+        old: i32,
+    },
+    Damage {
+        damage: i32,
+        is_crit: bool,
+        is_tenderized: bool,
+        attack_id: i32,
+        target: usize,
+    },
+    MonsterSpawned {
+        /// 怪物地址
+        monster: usize,
+        monster_id: i32,
+        variant: i32,
+    },
+    MonsterDespawned {
+        /// 怪物地址
+        monster: usize,
+        /// 怪物ID，由钩子发出时为 -1，事件处理器根据创建时的记录补全
+        monster_id: i32,
+    },
+    MonsterStateChanged {
+        /// 怪物地址
+        monster: usize,
+    },
+    PlayerFainted {
+        /// 本次任务的猫车次数
+        faint_count: i32,
+    },
+    PlayerVitals,
+    SpecializedTool,
+    StatusEffectChanged {
+        /// 状态名称
+        name: String,
+        /// 是否开始生效
+        active: bool,
+    },
+    SharpnessChanged,
 }
 
 impl Event {
@@ -27,13 +97,29 @@ impl Event {
             Event::LoadTriggers { .. } => EventType::LoadTriggers,
             Event::UpdateContext { .. } => EventType::UpdateContext,
             Event::LongswordLevelChanged { .. } => EventType::LongswordLevelChanged,
+            Event::LongswordSpiritChanged { .. } => EventType::LongswordSpiritChanged,
             Event::WeaponTypeChanged { .. } => EventType::WeaponTypeChanged,
             Event::QuestStateChanged { .. } => EventType::QuestStateChanged,
             Event::FsmChanged { .. } => EventType::FsmChanged,
             Event::InsectGlaive { .. } => EventType::InsectGlaive,
             Event::ChargeBlade { .. } => EventType::ChargeBlade,
+            Event::SwitchAxe { .. } => EventType::SwitchAxe,
+            Event::Gunlance { .. } => EventType::Gunlance,
+            Event::HuntingHornSongPerformed { .. } => EventType::HuntingHornSongPerformed,
+            Event::HuntingHornQueueChanged { .. } => EventType::HuntingHornQueueChanged,
+            Event::RangedWeapon { .. } => EventType::RangedWeapon,
+            Event::DualBlades { .. } => EventType::DualBlades,
+            Event::ChargeLevelChanged { .. } => EventType::ChargeLevelChanged,
             Event::UseItem { .. } => EventType::UseItem,
             Event::Damage { .. } => EventType::Damage,
+            Event::MonsterSpawned { .. } => EventType::MonsterSpawned,
+            Event::MonsterDespawned { .. } => EventType::MonsterDespawned,
+            Event::MonsterStateChanged { .. } => EventType::MonsterStateChanged,
+            Event::PlayerFainted { .. } => EventType::PlayerFainted,
+            Event::PlayerVitals { .. } => EventType::PlayerVitals,
+            Event::SpecializedTool { .. } => EventType::SpecializedTool,
+            Event::StatusEffectChanged { .. } => EventType::StatusEffectChanged,
+            Event::SharpnessChanged { .. } => EventType::SharpnessChanged,
         }
     }
 }
@@ -44,11 +130,27 @@ pub enum EventType {
     LoadTriggers,
     UpdateContext,
     LongswordLevelChanged,
+    LongswordSpiritChanged,
     WeaponTypeChanged,
     QuestStateChanged,
     FsmChanged,
     UseItem,
     InsectGlaive,
     ChargeBlade,
+    SwitchAxe,
+    Gunlance,
+    HuntingHornSongPerformed,
+    HuntingHornQueueChanged,
+    RangedWeapon,
+    DualBlades,
+    ChargeLevelChanged,
     Damage,
+    MonsterSpawned,
+    MonsterDespawned,
+    MonsterStateChanged,
+    PlayerFainted,
+    PlayerVitals,
+    SpecializedTool,
+    StatusEffectChanged,
+    SharpnessChanged,
 }
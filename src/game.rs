@@ -9,23 +9,95 @@ use mhw_toolkit::{
 use once_cell::sync::Lazy;
 use tokio::sync::{Mutex, Notify};
 
-use crate::game_context::{ChargeBlade, ChatCommand, Fsm, InsectGlaive, SpecializedTool};
+use crate::{
+    chat_queue::ChatQueue,
+    configs::{DamageConfig, MoveName},
+    damage_ledger::{DamageData, DamageLedger},
+    game_context::{
+        self, ChargeBlade, ChatCommand, DualBlades, Fsm, Gunlance, HuntingHorn, InsectGlaive, Longsword, Mantle,
+        Monster, PlayerVitals, RangedWeapon, Sharpness, SpecializedTool, SwitchAxe,
+    },
+};
 
 const QUEST_BASE: *const i32 = game_export::QUEST_BASE as *const i32;
 const WEAPON_DATA_BASE: *const i32 = game_export::PLAYER_BASE as *const i32;
 const PLAYER_BASE: *const i32 = game_export::PLAYER_BASE as *const i32;
 const QUEST_OFFSETS: isize = 0x38;
+const QUEST_FAINT_COUNT_OFFSETS: isize = 0x2BC;
 const PLAYER_FSMTARGET_OFFSETS: &[isize] = &[0x50, 0x6274];
 const PLAYER_FSMID_OFFSETS: &[isize] = &[0x50, 0x6278];
+const PLAYER_HEALTH_OFFSETS: &[isize] = &[0x50, 0x7630, 0x64];
+const PLAYER_MAX_HEALTH_OFFSETS: &[isize] = &[0x50, 0x7630, 0x60];
+const PLAYER_RED_HEALTH_OFFSETS: &[isize] = &[0x50, 0x7630, 0x2CE8];
+const PLAYER_STAMINA_OFFSETS: &[isize] = &[0x50, 0x7D20, 0x13C];
+const SPECIALIZED_TOOL_ID_OFFSETS: &[isize] = &[0x50, 0x13F28];
+const SPECIALIZED_TOOL_COOLDOWN_OFFSETS: &[isize] = &[0x50, 0x12898];
+const SPECIALIZED_TOOL_ACTIVE_OFFSETS: &[isize] = &[0x50, 0x12A20];
+/// 衣装ID上限
+const MAX_SPECIALIZED_TOOL_ID: i32 = 32;
+const PLAYER_STATUS_OFFSETS: &[isize] = &[0x50, 0x7D20];
+// 15.22
+const FOOD_SKILLS_OFFSETS: &[isize] = &[0x50, 0x7D20, 0x6F4];
+/// 猫饭技能槽数量
+const MAX_FOOD_SKILLS: isize = 5;
 const USE_ITEM_OFFSETS: &[isize] = &[0x50, 0x80, 0x80, 0x10, 0x288, 0x28E0];
 
 const LONGSWORD_OFFSETS: &[isize] = &[0x50, 0x468, 0x70, 0x10, 0x18, 0x2370];
 const WEAPON_OFFSETS: &[isize] = &[0x50, 0xC0, 0x8, 0x78, 0x2E8];
 const WEAPON_DATA_OFFSETS: &[isize] = &[0x50, 0x76B0];
 
+// 15.22，以下偏移均相对 WEAPON_DATA_OFFSETS 指向的武器数据
+// 各武器共用同一块数据区并按武器类型解释，因此不同武器的字段可以有相同偏移（如 0x2368）
+const LONGSWORD_SPIRIT_GAUGE_OFFSET: isize = 0x2368;
+const LONGSWORD_LEVEL_TIMER_OFFSET: isize = 0x236C;
+
+const SHARPNESS_COLOR_OFFSET: isize = 0x20F4;
+const SHARPNESS_REMAINING_OFFSET: isize = 0x20F8;
+
+const SWITCH_AXE_SWITCH_GAUGE_OFFSET: isize = 0x2380;
+const SWITCH_AXE_AMP_GAUGE_OFFSET: isize = 0x2384;
+const SWITCH_AXE_AMPED_TIMER_OFFSET: isize = 0x2388;
+const SWITCH_AXE_POWER_AXE_TIMER_OFFSET: isize = 0x238C;
+
+const GUNLANCE_SHELLS_OFFSET: isize = 0x2368;
+const GUNLANCE_MAX_SHELLS_OFFSET: isize = 0x236C;
+const GUNLANCE_STAKE_LOADED_OFFSET: isize = 0x2370;
+const GUNLANCE_WYVERNFIRE_COOLDOWN_OFFSET: isize = 0x2374;
+const GUNLANCE_WYRMSTAKE_LOADED_OFFSET: isize = 0x2378;
+
+const HUNTING_HORN_NOTES_OFFSET: isize = 0x2368;
+const HUNTING_HORN_NOTE_COUNT_OFFSET: isize = 0x2378;
+const HUNTING_HORN_SONG_QUEUE_OFFSET: isize = 0x237C;
+const HUNTING_HORN_SONG_COUNT_OFFSET: isize = 0x2388;
+/// 音符输入槽容量
+const HUNTING_HORN_MAX_NOTES: usize = 4;
+
+const RANGED_AMMO_TYPE_OFFSET: isize = 0x2390;
+const RANGED_LOADED_OFFSET: isize = 0x2394;
+const RANGED_RESERVE_OFFSET: isize = 0x2398;
+const BOW_CHARGE_LEVEL_OFFSET: isize = 0x239C;
+
+const CHARGE_LEVEL_OFFSET: isize = 0x2368;
+const DUAL_BLADES_DEMON_MODE_OFFSET: isize = 0x2368;
+const DUAL_BLADES_ARCHDEMON_GAUGE_OFFSET: isize = 0x236C;
+const DUAL_BLADES_STAMINA_DRAIN_OFFSET: isize = 0x2370;
+
 const CHARGE_BLADE_BASE: *const i32 = 0x1450EC750 as *const i32; // 0x1450EA510 15.22
 const CHARGE_BLADE_MAX_PHIALS_OFFSETS: &[isize] = &[0x110, 0x98, 0x58, 0x5F98];
 
+// 15.22
+const MONSTER_LIST_BASE: *const usize = 0x1451C2400 as *const usize;
+const MONSTER_LIST_OFFSETS: &[isize] = &[0x698, 0x0, 0x138];
+const MONSTER_NEXT_OFFSET: isize = 0x28;
+const MONSTER_ID_OFFSET: isize = 0x12280;
+const MONSTER_HEALTH_OFFSETS: &[isize] = &[0x7670, 0x64];
+const MONSTER_MAX_HEALTH_OFFSETS: &[isize] = &[0x7670, 0x60];
+const MONSTER_RAGE_TIMER_OFFSET: isize = 0x1BE30;
+const MONSTER_FATIGUE_TIMER_OFFSET: isize = 0x1C0B8;
+const MONSTER_TRAPPED_OFFSET: isize = 0x1C3E8;
+/// 最多追踪的怪物数量
+const MAX_MONSTERS: usize = 8;
+
 const CHAT_COMMAND_PREFIX: &str = "!mas ";
 static CHAT_MESSAGE_RECV: Lazy<game_util::ChatMessageReceiver> = Lazy::new(|| {
     let mut instance = game_util::ChatMessageReceiver::new();
@@ -35,37 +107,118 @@ static CHAT_MESSAGE_RECV: Lazy<game_util::ChatMessageReceiver> = Lazy::new(|| {
 static DAMAGE_COLLECTOR: Lazy<Arc<DamageCollector>> = Lazy::new(|| Arc::new(DamageCollector::new()));
 
 pub struct DamageCollector {
-    records: Mutex<HashMap<Fsm, Vec<DamageData>>>,
     fsm_notify: Notify,
-    simple_collector: Mutex<Vec<DamageData>>,
+    ledger: Mutex<DamageLedger>,
     now_fsm: Mutex<Fsm>,
+    quest_stats: Mutex<QuestDamageStats>,
+    move_names: Mutex<HashMap<Fsm, String>>,
+    /// 存活的怪物：地址 -> 怪物ID
+    live_monsters: Mutex<HashMap<usize, i32>>,
 }
 
 impl DamageCollector {
     fn new() -> Self {
+        let default_cfg = DamageConfig::default();
         DamageCollector {
-            records: Mutex::new(HashMap::new()),
             fsm_notify: Notify::new(),
-            simple_collector: Mutex::new(Vec::new()),
+            ledger: Mutex::new(DamageLedger::new(retention_delta(default_cfg.retention), default_cfg.max_records)),
             now_fsm: Mutex::new(Fsm::default()),
+            quest_stats: Mutex::new(QuestDamageStats::default()),
+            move_names: Mutex::new(HashMap::new()),
+            live_monsters: Mutex::new(HashMap::new()),
         }
     }
 
+    /// 应用配置
+    pub async fn configure(&self, cfg: &DamageConfig) {
+        self.ledger.lock().await.set_limits(retention_delta(cfg.retention), cfg.max_records);
+    }
+
+    /// 设置动作名称
+    pub async fn set_move_names(&self, move_names: &[MoveName]) {
+        *self.move_names.lock().await = move_names
+            .iter()
+            .map(|m| {
+                let fsm = Fsm {
+                    target: m.target,
+                    id: m.id,
+                };
+                (fsm, m.name.clone())
+            })
+            .collect();
+    }
+
     /// 接收伤害事件
-    pub async fn on_damage(&self, damage: i32) {
+    pub async fn on_damage(&self, damage: i32, is_crit: bool, is_tenderized: bool, target: usize) {
         let fsm = get_fsm();
         debug!("DamageCollector: on damage {} <=> {:?}", damage, fsm);
         // 记录伤害
-        let data = DamageData::new(damage, &fsm);
-        self.records.lock().await.entry(fsm).or_insert_with(Vec::new).push(data.clone());
-        self.simple_collector.lock().await.push(data);
+        let data = DamageData {
+            damage,
+            fsm,
+            time: Utc::now(),
+            is_crit,
+            is_tenderized,
+            target,
+        };
+        self.quest_stats.lock().await.record(&data);
+        self.ledger.lock().await.push(data);
+    }
+
+    /// 接收怪物创建事件
+    pub async fn on_monster_spawned(&self, monster: usize, monster_id: i32) {
+        self.live_monsters.lock().await.insert(monster, monster_id);
+    }
+
+    /// 接收怪物销毁事件
+    ///
+    /// 之后相同地址上创建的怪物会作为新的怪物统计，返回记录的怪物ID
+    pub async fn on_monster_despawned(&self, monster: usize) -> Option<i32> {
+        let monster_id = self.live_monsters.lock().await.remove(&monster)?;
+        self.quest_stats.lock().await.despawn_monster(monster);
+        Some(monster_id)
+    }
+
+    /// 当前存活的怪物数量
+    pub async fn live_monster_count(&self) -> usize {
+        self.live_monsters.lock().await.len()
+    }
+
+    /// 接收任务状态变更事件
+    pub async fn on_quest_state_changed(&self, new: i32) {
+        let mut quest_stats = self.quest_stats.lock().await;
+        match new {
+            // 进入任务时重新开始统计
+            2 => *quest_stats = QuestDamageStats::new(Utc::now()),
+            // 任务完成时停止统计
+            3 => quest_stats.finish(Utc::now()),
+            // 回到据点
+            1 => *quest_stats = QuestDamageStats::default(),
+            _ => {}
+        }
+    }
+
+    /// 获取动作名称
+    pub async fn move_name(&self, fsm: &Fsm) -> String {
+        move_name_of(&self.move_names.lock().await, fsm)
+    }
+
+    /// 获取怪物在本次任务中的编号（从1开始）和累计伤害
+    pub async fn monster_damage(&self, target: usize) -> Option<(usize, i64)> {
+        self.quest_stats.lock().await.monster_damage(target)
+    }
+
+    /// 获取本次任务伤害总结
+    pub async fn quest_summary(&self) -> QuestSummary {
+        let quest_stats = self.quest_stats.lock().await.clone();
+        let move_names = self.move_names.lock().await;
+        quest_stats.summary(&move_names, Utc::now())
     }
 
     /// 接收fsm变更事件
     pub async fn on_fsm_changed(&self, fsm_after: &Fsm) {
-        self.records.lock().await.remove(fsm_after);
         *self.now_fsm.lock().await = *fsm_after;
-        self.clear_expired_data().await;
+        self.ledger.lock().await.clear_expired(Utc::now());
         self.fsm_notify.notify_waiters();
     }
 
@@ -74,96 +227,288 @@ impl DamageCollector {
         DAMAGE_COLLECTOR.clone()
     }
 
-    /// 获取某个fsm的收集值
-    async fn collect_one(&self, fsm: &Fsm) -> i32 {
-        if let Some(record) = self.records.lock().await.get(fsm) {
-            record.iter().map(|data| data.damage).sum()
-        } else {
-            0
+    /// 收集某个时间段的伤害
+    async fn _collect_duration<F>(
+        &self,
+        start_time: DateTime<Utc>,
+        end_time: DateTime<Utc>,
+        filter: &DamageFilter,
+        predicate: F,
+    ) -> DamageStats
+    where
+        F: Fn(&DamageData) -> bool,
+    {
+        let ledger = self.ledger.lock().await;
+        let records: Vec<&DamageData> =
+            ledger.range(start_time, end_time).filter(|data| filter.matches(data) && predicate(data)).collect();
+        // 仅统计与首次命中相同的目标
+        let first_target = records.first().map(|data| data.target);
+        let records = records.into_iter().filter(|data| !filter.same_monster || Some(data.target) == first_target);
+
+        DamageStats::from_records(records)
+    }
+
+    /// 收集从开始时间起，处于指定fsm期间的伤害
+    ///
+    /// 当前fsm不再满足 `is_fsm` 或超时后结束收集，只统计满足 `is_fsm` 的动作造成的伤害
+    pub async fn collect_fsm<F>(
+        &self,
+        is_fsm: F,
+        start_time: DateTime<Utc>,
+        timeout_dur: Duration,
+        filter: &DamageFilter,
+    ) -> DamageStats
+    where
+        F: Fn(&Fsm) -> bool + Send + Sync,
+    {
+        let deadline = start_time + timeout_dur;
+        let remaining = (deadline - Utc::now()).to_std().unwrap_or_default();
+        // 超时后直接返回当前收集值
+        let _ = tokio::time::timeout(remaining, async {
+            loop {
+                // fsm变化通知
+                let notified = self.fsm_notify.notified();
+                if !is_fsm(&*self.now_fsm.lock().await) {
+                    return;
+                }
+                notified.await;
+            }
+        })
+        .await;
+        let end_time = Utc::now().min(deadline);
+        self._collect_duration(start_time, end_time, filter, |data| is_fsm(&data.fsm)).await
+    }
+
+    /// 收集从开始时间起一段时间内的伤害
+    pub async fn collect_time(
+        &self,
+        start_time: DateTime<Utc>,
+        duration: Duration,
+        filter: &DamageFilter,
+    ) -> DamageStats {
+        let end_time = start_time + duration;
+        tokio::time::sleep((end_time - Utc::now()).to_std().unwrap_or_default()).await;
+        // 收集伤害
+        self._collect_duration(start_time, end_time, filter, |_| true).await
+    }
+}
+
+/// 任务伤害统计
+///
+/// 记录整个任务的累计数据，不受伤害记录保留时间影响
+#[derive(Clone, Debug, Default)]
+struct QuestDamageStats {
+    start_time: Option<DateTime<Utc>>,
+    end_time: Option<DateTime<Utc>>,
+    total_damage: i64,
+    hits: i32,
+    max_hit: i32,
+    max_hit_fsm: Fsm,
+    /// 每个动作的累计伤害
+    moves: HashMap<Fsm, i64>,
+    /// 每个怪物的累计伤害，按首次命中顺序排列
+    monsters: Vec<MonsterDamage>,
+}
+
+/// 单个怪物的累计伤害
+#[derive(Clone, Debug)]
+struct MonsterDamage {
+    /// 怪物地址
+    target: usize,
+    damage: i64,
+    /// 是否已销毁
+    despawned: bool,
+}
+
+impl QuestDamageStats {
+    fn new(start_time: DateTime<Utc>) -> Self {
+        QuestDamageStats {
+            start_time: Some(start_time),
+            ..Default::default()
         }
     }
 
-    /// 收集某个时间段的伤害
-    async fn _collect_duration(&self, start_time: DateTime<Utc>, end_time: DateTime<Utc>) -> i32 {
-        let mut result_damage = 0;
-        let simple_collector = self.simple_collector.lock().await;
-        // 使用二分搜索确定开始时间的索引
-        let start_idx = match simple_collector.binary_search_by(|data| data.time.cmp(&start_time)) {
-            Ok(index) => index,
-            Err(index) => index,
-        };
+    fn record(&mut self, data: &DamageData) {
+        if self.end_time.is_some() {
+            return;
+        }
+        // 中途加载插件时，以首次伤害作为开始时间
+        self.start_time.get_or_insert(data.time);
+        self.total_damage += data.damage as i64;
+        self.hits += 1;
+        if data.damage > self.max_hit {
+            self.max_hit = data.damage;
+            self.max_hit_fsm = data.fsm;
+        }
+        *self.moves.entry(data.fsm).or_default() += data.damage as i64;
+        match self.monsters.iter_mut().find(|m| !m.despawned && m.target == data.target) {
+            Some(monster) => monster.damage += data.damage as i64,
+            None => self.monsters.push(MonsterDamage {
+                target: data.target,
+                damage: data.damage as i64,
+                despawned: false,
+            }),
+        }
+    }
 
-        // 遍历从开始时间到结束时间的数据
-        for data in simple_collector[start_idx..].iter() {
-            if data.time > end_time {
-                break; // 超出结束时间，停止遍历
-            }
-            result_damage += data.damage;
+    fn despawn_monster(&mut self, target: usize) {
+        for monster in self.monsters.iter_mut().filter(|m| m.target == target) {
+            monster.despawned = true;
         }
+    }
 
-        result_damage
+    /// 同一地址有多个怪物时取最后一个
+    fn monster_damage(&self, target: usize) -> Option<(usize, i64)> {
+        self.monsters
+            .iter()
+            .enumerate()
+            .rfind(|(_, m)| m.target == target)
+            .map(|(idx, m)| (idx + 1, m.damage))
     }
 
-    /// 清除过期的数据
-    async fn clear_expired_data(&self) {
-        let now = Utc::now();
-        let mut simple_collector = self.simple_collector.lock().await;
-        let mut cut_index = 0;
-        for (idx, data) in simple_collector.iter().enumerate() {
-            // 清除60秒之前的数据
-            if data.time + TimeDelta::try_minutes(60).unwrap() < now {
-                cut_index = idx;
-                break;
-            }
+    fn finish(&mut self, end_time: DateTime<Utc>) {
+        if self.end_time.is_none() {
+            self.end_time = Some(end_time);
         }
-        simple_collector.drain(0..cut_index);
     }
 
-    /// 收集某个fsm期间的伤害
-    pub async fn collect_fsm(&self, fsm: &Fsm, timeout_dur: Duration) -> i32 {
-        match tokio::time::timeout(timeout_dur, async {
-            loop {
-                // fsm变化通知
-                self.fsm_notify.notified().await;
-                if *self.now_fsm.lock().await != *fsm {
-                    return self.collect_one(fsm).await;
-                }
-            }
-        })
-        .await
-        {
-            Ok(damage) => damage,
-            Err(_) => {
-                // 超时返回当前收集值
-                self.collect_one(fsm).await
-            }
+    fn summary(&self, move_names: &HashMap<Fsm, String>, now: DateTime<Utc>) -> QuestSummary {
+        let name_of = |fsm: &Fsm| move_name_of(move_names, fsm);
+        let quest_time = match self.start_time {
+            Some(start_time) => (self.end_time.unwrap_or(now) - start_time).num_milliseconds().max(0) as f64 / 1000.0,
+            None => 0.0,
+        };
+        // 同名动作合并统计
+        let mut moves: HashMap<String, i64> = HashMap::new();
+        for (fsm, damage) in self.moves.iter() {
+            *moves.entry(name_of(fsm)).or_default() += damage;
+        }
+        let mut top_moves: Vec<(String, i64)> = moves.into_iter().collect();
+        top_moves.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        top_moves.truncate(3);
+
+        QuestSummary {
+            total_damage: self.total_damage,
+            dps: if quest_time > 0.0 {
+                self.total_damage as f64 / quest_time
+            } else {
+                0.0
+            },
+            quest_time,
+            hits: self.hits,
+            max_hit: self.max_hit,
+            max_hit_move: if self.hits > 0 {
+                name_of(&self.max_hit_fsm)
+            } else {
+                String::new()
+            },
+            top_moves,
+            monsters: self.monsters.iter().map(|m| m.damage).collect(),
         }
     }
+}
 
-    /// 收集从现在开始一段时间内的伤害
-    pub async fn collect_time(&self, duration: Duration) -> i32 {
-        let start_time = Utc::now();
-        let end_time = start_time + duration;
-        tokio::time::sleep(duration).await;
-        // 收集伤害
-        self._collect_duration(start_time, end_time).await
+/// 任务伤害总结
+#[derive(Clone, Debug, Default)]
+pub struct QuestSummary {
+    /// 总伤害
+    pub total_damage: i64,
+    /// 每秒伤害
+    pub dps: f64,
+    /// 任务用时（秒）
+    pub quest_time: f64,
+    /// 命中次数
+    pub hits: i32,
+    /// 单次最高伤害
+    pub max_hit: i32,
+    /// 单次最高伤害的动作
+    pub max_hit_move: String,
+    /// 伤害最高的三个动作
+    pub top_moves: Vec<(String, i64)>,
+    /// 每个怪物的累计伤害，按首次命中顺序排列
+    pub monsters: Vec<i64>,
+}
+
+/// 获取动作名称，未命名的动作使用 `target:id`
+fn move_name_of(move_names: &HashMap<Fsm, String>, fsm: &Fsm) -> String {
+    match move_names.get(fsm) {
+        Some(name) => name.clone(),
+        None => format!("{}:{}", fsm.target, fsm.id),
     }
 }
 
-#[allow(dead_code)]
-#[derive(Clone)]
-struct DamageData {
-    damage: i32,
-    fsm: Fsm,
-    time: DateTime<Utc>,
+fn retention_delta(retention: f32) -> TimeDelta {
+    TimeDelta::try_milliseconds((retention * 1000.0) as i64).unwrap_or_default()
 }
 
-impl DamageData {
-    pub fn new(damage: i32, fsm: &Fsm) -> Self {
-        DamageData {
-            damage,
-            fsm: *fsm,
-            time: Utc::now(),
+/// 伤害过滤条件
+///
+/// 为 `None` 的条件不参与过滤
+#[derive(Clone, Debug, Default)]
+pub struct DamageFilter {
+    pub crit: Option<bool>,
+    pub tenderized: Option<bool>,
+    /// 仅统计与首次命中相同的目标
+    pub same_monster: bool,
+}
+
+impl DamageFilter {
+    fn matches(&self, data: &DamageData) -> bool {
+        self.matches_hit(data.is_crit, data.is_tenderized)
+    }
+
+    pub fn matches_hit(&self, is_crit: bool, is_tenderized: bool) -> bool {
+        self.crit.map_or(true, |crit| crit == is_crit)
+            && self.tenderized.map_or(true, |tenderized| tenderized == is_tenderized)
+    }
+}
+
+/// 伤害统计结果
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DamageStats {
+    /// 总伤害
+    pub damage: i32,
+    /// 命中次数
+    pub hits: i32,
+    /// 会心次数
+    pub crits: i32,
+    /// 单次最高伤害
+    pub max_hit: i32,
+    /// 单次最低伤害
+    pub min_hit: i32,
+    /// 每次命中的伤害
+    pub hit_list: Vec<i32>,
+    /// 首次命中的目标
+    pub first_target: Option<usize>,
+}
+
+impl DamageStats {
+    fn from_records<'a>(records: impl Iterator<Item = &'a DamageData>) -> Self {
+        records.fold(DamageStats::default(), |mut stats, data| {
+            if stats.hits == 0 {
+                stats.max_hit = data.damage;
+                stats.min_hit = data.damage;
+                stats.first_target = Some(data.target);
+            } else {
+                stats.max_hit = stats.max_hit.max(data.damage);
+                stats.min_hit = stats.min_hit.min(data.damage);
+            }
+            stats.damage += data.damage;
+            stats.hits += 1;
+            if data.is_crit {
+                stats.crits += 1;
+            }
+            stats.hit_list.push(data.damage);
+            stats
+        })
+    }
+
+    /// 平均单次伤害
+    pub fn avg_hit(&self) -> i32 {
+        if self.hits == 0 {
+            0
+        } else {
+            self.damage / self.hits
         }
     }
 }
@@ -174,7 +519,7 @@ pub fn get_chat_command() -> Option<ChatCommand> {
         let cmd = ChatCommand::from_str(&msg[CHAT_COMMAND_PREFIX.len()..]);
         if cmd.is_none() {
             info!("无效的命令：{}", msg);
-            game_util::send_chat_message("无效的命令");
+            tokio::spawn(async { ChatQueue::instance().push("无效的命令".to_string(), 0, None).await });
         }
         cmd
     } else {
@@ -186,10 +531,59 @@ pub fn get_quest_state() -> i32 {
     utils::get_value_with_offset(QUEST_BASE, &[QUEST_OFFSETS]).unwrap_or(0)
 }
 
+pub fn get_faint_count() -> i32 {
+    utils::get_value_with_offset(QUEST_BASE, &[QUEST_FAINT_COUNT_OFFSETS]).unwrap_or(0)
+}
+
 pub fn get_longsword_level() -> i32 {
     utils::get_value_with_offset(WEAPON_DATA_BASE, LONGSWORD_OFFSETS).unwrap_or(99)
 }
 
+/// 大剑和大锤的蓄力等级
+pub fn get_charge_level() -> Option<i32> {
+    let weapon_data_base = utils::get_ptr_with_offset(WEAPON_DATA_BASE as *const f32, WEAPON_DATA_OFFSETS)?;
+    utils::get_value_with_offset(weapon_data_base as *const i32, &[CHARGE_LEVEL_OFFSET])
+}
+
+pub fn get_dual_blades_data() -> Option<DualBlades> {
+    let weapon_data_base = utils::get_ptr_with_offset(WEAPON_DATA_BASE as *const f32, WEAPON_DATA_OFFSETS)?;
+    let data: DualBlades = DualBlades {
+        demon_mode: utils::get_value_with_offset(weapon_data_base as *const u8, &[DUAL_BLADES_DEMON_MODE_OFFSET])
+            .unwrap_or(0)
+            != 0,
+        archdemon_gauge: utils::get_value_with_offset(weapon_data_base, &[DUAL_BLADES_ARCHDEMON_GAUGE_OFFSET])
+            .unwrap_or(0.0),
+        stamina_drain: utils::get_value_with_offset(weapon_data_base, &[DUAL_BLADES_STAMINA_DRAIN_OFFSET])
+            .unwrap_or(0.0),
+    };
+
+    Some(data)
+}
+
+pub fn get_longsword_data() -> Option<Longsword> {
+    let weapon_data_base = utils::get_ptr_with_offset(WEAPON_DATA_BASE as *const f32, WEAPON_DATA_OFFSETS)?;
+    // 气刃槽原始值为 0-1
+    let spirit_gauge: f32 =
+        utils::get_value_with_offset(weapon_data_base, &[LONGSWORD_SPIRIT_GAUGE_OFFSET]).unwrap_or(0.0);
+    let data = Longsword {
+        spirit_gauge: spirit_gauge * 100.0,
+        level_timer: utils::get_value_with_offset(weapon_data_base, &[LONGSWORD_LEVEL_TIMER_OFFSET]).unwrap_or(0.0),
+    };
+
+    Some(data)
+}
+
+pub fn get_sharpness() -> Option<Sharpness> {
+    let weapon_data_base = utils::get_ptr_with_offset(WEAPON_DATA_BASE as *const f32, WEAPON_DATA_OFFSETS)?;
+    let int_base = weapon_data_base as *const i32;
+    let data = Sharpness {
+        color: utils::get_value_with_offset(int_base, &[SHARPNESS_COLOR_OFFSET]).unwrap_or(0),
+        remaining: utils::get_value_with_offset(int_base, &[SHARPNESS_REMAINING_OFFSET]).unwrap_or(0),
+    };
+
+    Some(data)
+}
+
 pub fn get_weapon_type() -> Option<WeaponType> {
     let weapon_type_id = utils::get_value_with_offset(WEAPON_DATA_BASE, WEAPON_OFFSETS).unwrap_or(0);
     WeaponType::from_i32(weapon_type_id)
@@ -201,15 +595,22 @@ pub fn get_fsm() -> Fsm {
     Fsm { id, target }
 }
 
+pub fn get_player_vitals() -> PlayerVitals {
+    let player_base = PLAYER_BASE as *const f32;
+    PlayerVitals {
+        health: utils::get_value_with_offset(player_base, PLAYER_HEALTH_OFFSETS).unwrap_or(0.0),
+        max_health: utils::get_value_with_offset(player_base, PLAYER_MAX_HEALTH_OFFSETS).unwrap_or(0.0),
+        red_health: utils::get_value_with_offset(player_base, PLAYER_RED_HEALTH_OFFSETS).unwrap_or(0.0),
+        stamina: utils::get_value_with_offset(player_base, PLAYER_STAMINA_OFFSETS).unwrap_or(0.0),
+    }
+}
+
 pub fn get_use_item_id() -> i32 {
     utils::get_value_with_offset(PLAYER_BASE, USE_ITEM_OFFSETS).unwrap_or(-1)
 }
 
 pub fn get_insect_glaive_data() -> Option<InsectGlaive> {
-    let weapon_data_base = match utils::get_ptr_with_offset(WEAPON_DATA_BASE as *const f32, WEAPON_DATA_OFFSETS) {
-        Some(ptr) => ptr,
-        None => return None,
-    };
+    let weapon_data_base = utils::get_ptr_with_offset(WEAPON_DATA_BASE as *const f32, WEAPON_DATA_OFFSETS)?;
     let data: InsectGlaive = InsectGlaive {
         attack_timer: utils::get_value_with_offset(weapon_data_base, &[0x2368]).unwrap_or(0.0),
         speed_timer: utils::get_value_with_offset(weapon_data_base, &[0x236C]).unwrap_or(0.0),
@@ -220,23 +621,139 @@ pub fn get_insect_glaive_data() -> Option<InsectGlaive> {
 }
 
 pub fn get_charge_blade_data() -> Option<ChargeBlade> {
-    let weapon_data_base = match utils::get_ptr_with_offset(WEAPON_DATA_BASE as *const f32, WEAPON_DATA_OFFSETS) {
-        Some(ptr) => ptr,
-        None => return None,
-    };
+    let weapon_data_base = utils::get_ptr_with_offset(WEAPON_DATA_BASE as *const f32, WEAPON_DATA_OFFSETS)?;
+    let int_base = weapon_data_base as *const i32;
     let data: ChargeBlade = ChargeBlade {
         sword_power: utils::get_value_with_offset(weapon_data_base, &[0x2370]).unwrap_or(0.0),
         sword_charge_timer: utils::get_value_with_offset(weapon_data_base, &[0x237C]).unwrap_or(0.0),
         shield_charge_timer: utils::get_value_with_offset(weapon_data_base, &[0x2378]).unwrap_or(0.0),
-        phials: utils::get_value_with_offset(weapon_data_base as *const i32, &[0x2374]).unwrap_or(0),
+        phials: utils::get_value_with_offset(int_base, &[0x2374]).unwrap_or(0),
         max_phials: utils::get_value_with_offset(CHARGE_BLADE_BASE, CHARGE_BLADE_MAX_PHIALS_OFFSETS).unwrap_or(0),
-        power_axe_mode: utils::get_value_with_offset(weapon_data_base as *const i32, &[0x2474]).unwrap_or(0),
+        power_axe_mode: utils::get_value_with_offset(int_base, &[0x2474]).unwrap_or(0),
         power_axe_timer: utils::get_value_with_offset(weapon_data_base, &[0x2470]).unwrap_or(0.0),
     };
 
     Some(data)
 }
 
+/// 获取当前追踪的大型怪物
+pub fn get_monsters() -> Vec<Monster> {
+    let mut monsters = Vec::new();
+    let mut address = match utils::get_value_with_offset(MONSTER_LIST_BASE, MONSTER_LIST_OFFSETS) {
+        Some(address) => address,
+        None => return monsters,
+    };
+    while address != 0 && monsters.len() < MAX_MONSTERS {
+        let monster_base = address as *const i32;
+        let rage_timer: f32 =
+            utils::get_value_with_offset(monster_base as *const f32, &[MONSTER_RAGE_TIMER_OFFSET]).unwrap_or(0.0);
+        let fatigue_timer: f32 =
+            utils::get_value_with_offset(monster_base as *const f32, &[MONSTER_FATIGUE_TIMER_OFFSET]).unwrap_or(0.0);
+        monsters.push(Monster {
+            address,
+            monster_id: utils::get_value_with_offset(monster_base, &[MONSTER_ID_OFFSET]).unwrap_or(-1),
+            health: utils::get_value_with_offset(monster_base as *const f32, MONSTER_HEALTH_OFFSETS).unwrap_or(0.0),
+            max_health: utils::get_value_with_offset(monster_base as *const f32, MONSTER_MAX_HEALTH_OFFSETS)
+                .unwrap_or(0.0),
+            is_enraged: rage_timer > 0.0,
+            is_exhausted: fatigue_timer > 0.0,
+            is_trapped: utils::get_value_with_offset(monster_base, &[MONSTER_TRAPPED_OFFSET]).unwrap_or(0) != 0,
+        });
+        address = utils::get_value_with_offset(address as *const usize, &[MONSTER_NEXT_OFFSET]).unwrap_or(0);
+    }
+
+    monsters
+}
+
+pub fn get_switch_axe_data() -> Option<SwitchAxe> {
+    let weapon_data_base = utils::get_ptr_with_offset(WEAPON_DATA_BASE as *const f32, WEAPON_DATA_OFFSETS)?;
+    let data: SwitchAxe = SwitchAxe {
+        switch_gauge: utils::get_value_with_offset(weapon_data_base, &[SWITCH_AXE_SWITCH_GAUGE_OFFSET]).unwrap_or(0.0),
+        amp_gauge: utils::get_value_with_offset(weapon_data_base, &[SWITCH_AXE_AMP_GAUGE_OFFSET]).unwrap_or(0.0),
+        amped_timer: utils::get_value_with_offset(weapon_data_base, &[SWITCH_AXE_AMPED_TIMER_OFFSET]).unwrap_or(0.0),
+        power_axe_timer: utils::get_value_with_offset(weapon_data_base, &[SWITCH_AXE_POWER_AXE_TIMER_OFFSET])
+            .unwrap_or(0.0),
+    };
+
+    Some(data)
+}
+
+pub fn get_gunlance_data() -> Option<Gunlance> {
+    let weapon_data_base = utils::get_ptr_with_offset(WEAPON_DATA_BASE as *const f32, WEAPON_DATA_OFFSETS)?;
+    let int_base = weapon_data_base as *const i32;
+    let data: Gunlance = Gunlance {
+        shells: utils::get_value_with_offset(int_base, &[GUNLANCE_SHELLS_OFFSET]).unwrap_or(0),
+        max_shells: utils::get_value_with_offset(int_base, &[GUNLANCE_MAX_SHELLS_OFFSET]).unwrap_or(0),
+        stake_loaded: utils::get_value_with_offset(int_base, &[GUNLANCE_STAKE_LOADED_OFFSET]).unwrap_or(0) != 0,
+        wyvernfire_cooldown: utils::get_value_with_offset(weapon_data_base, &[GUNLANCE_WYVERNFIRE_COOLDOWN_OFFSET])
+            .unwrap_or(0.0),
+        wyrmstake_loaded: utils::get_value_with_offset(int_base, &[GUNLANCE_WYRMSTAKE_LOADED_OFFSET]).unwrap_or(0) != 0,
+    };
+
+    Some(data)
+}
+
+pub fn get_hunting_horn_data() -> Option<HuntingHorn> {
+    let weapon_data_base = utils::get_ptr_with_offset(WEAPON_DATA_BASE as *const f32, WEAPON_DATA_OFFSETS)?;
+    let int_base = weapon_data_base as *const i32;
+    let read_array = |offset: isize, count_offset: isize, max: usize| -> Vec<i32> {
+        let count = utils::get_value_with_offset(int_base, &[count_offset]).unwrap_or(0);
+        (0..count.clamp(0, max as i32) as isize)
+            .filter_map(|idx| utils::get_value_with_offset(int_base, &[offset + idx * 4]))
+            .collect()
+    };
+    let data: HuntingHorn = HuntingHorn {
+        notes: read_array(HUNTING_HORN_NOTES_OFFSET, HUNTING_HORN_NOTE_COUNT_OFFSET, HUNTING_HORN_MAX_NOTES),
+        song_queue: read_array(
+            HUNTING_HORN_SONG_QUEUE_OFFSET,
+            HUNTING_HORN_SONG_COUNT_OFFSET,
+            HuntingHorn::MAX_SONG_QUEUE,
+        ),
+    };
+
+    Some(data)
+}
+
+pub fn get_ranged_weapon_data() -> Option<RangedWeapon> {
+    let weapon_data_base = utils::get_ptr_with_offset(WEAPON_DATA_BASE as *const f32, WEAPON_DATA_OFFSETS)?;
+    let int_base = weapon_data_base as *const i32;
+    let data: RangedWeapon = RangedWeapon {
+        ammo_type: utils::get_value_with_offset(int_base, &[RANGED_AMMO_TYPE_OFFSET]).unwrap_or(0),
+        loaded: utils::get_value_with_offset(int_base, &[RANGED_LOADED_OFFSET]).unwrap_or(0),
+        reserve: utils::get_value_with_offset(int_base, &[RANGED_RESERVE_OFFSET]).unwrap_or(0),
+        charge_level: utils::get_value_with_offset(int_base, &[BOW_CHARGE_LEVEL_OFFSET]).unwrap_or(0),
+    };
+
+    Some(data)
+}
+
+/// 获取增益和异常状态的剩余时间
+pub fn get_status_effects() -> HashMap<String, f32> {
+    let Some(status_base) = utils::get_ptr_with_offset(PLAYER_BASE as *const f32, PLAYER_STATUS_OFFSETS) else {
+        return HashMap::new();
+    };
+    game_context::STATUS_EFFECTS
+        .iter()
+        .map(|def| {
+            let timer = utils::get_value_with_offset(status_base, &[def.offset]).unwrap_or(0.0);
+            (def.name.to_string(), timer)
+        })
+        .collect()
+}
+
+/// 获取生效中的猫饭技能ID
+///
+/// 空槽位和未定义的技能会被忽略
+pub fn get_food_skills() -> Vec<i32> {
+    let Some(skills_base) = utils::get_ptr_with_offset(PLAYER_BASE, FOOD_SKILLS_OFFSETS) else {
+        return Vec::new();
+    };
+    (0..MAX_FOOD_SKILLS)
+        .filter_map(|idx| utils::get_value_with_offset(skills_base, &[idx * 4]))
+        .filter(|id| game_context::find_food_skill_by_id(*id).is_some())
+        .collect()
+}
+
 /// 玩家是否加入了救难信号的房间
 pub fn is_player_join_mayday() -> bool {
     let Some(player) = Player::current_player() else {
@@ -253,7 +770,117 @@ pub fn is_player_join_mayday() -> bool {
     false
 }
 
-// TODO
 pub fn get_specialized_tool() -> Option<SpecializedTool> {
-    None
+    let ids_base = utils::get_ptr_with_offset(PLAYER_BASE, SPECIALIZED_TOOL_ID_OFFSETS)?;
+    let cooldown_base = utils::get_ptr_with_offset(PLAYER_BASE as *const f32, SPECIALIZED_TOOL_COOLDOWN_OFFSETS)?;
+    let active_base = utils::get_ptr_with_offset(PLAYER_BASE as *const f32, SPECIALIZED_TOOL_ACTIVE_OFFSETS)?;
+    let read_mantle = |slot: isize| {
+        let id = utils::get_value_with_offset(ids_base, &[slot * 4]).unwrap_or(-1);
+        if !(0..MAX_SPECIALIZED_TOOL_ID).contains(&id) {
+            return Mantle {
+                id: -1,
+                ..Default::default()
+            };
+        }
+        // 计时器按衣装ID排列
+        let timer_offset = id as isize * 4;
+        Mantle {
+            id,
+            active_timer: utils::get_value_with_offset(active_base, &[timer_offset]).unwrap_or(0.0),
+            cooldown_timer: utils::get_value_with_offset(cooldown_base, &[timer_offset]).unwrap_or(0.0),
+        }
+    };
+
+    Some(SpecializedTool {
+        slots: [read_mantle(0), read_mantle(1)],
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn damage_data(damage: i32, is_crit: bool) -> DamageData {
+        DamageData {
+            damage,
+            fsm: Fsm::default(),
+            time: Utc::now(),
+            is_crit,
+            is_tenderized: false,
+            target: 0,
+        }
+    }
+
+    #[test]
+    fn test_damage_stats() {
+        let records = [damage_data(300, false), damage_data(800, true), damage_data(100, false)];
+        let stats = DamageStats::from_records(records.iter());
+        assert_eq!(stats.damage, 1200);
+        assert_eq!(stats.hits, 3);
+        assert_eq!(stats.crits, 1);
+        assert_eq!(stats.max_hit, 800);
+        assert_eq!(stats.min_hit, 100);
+        assert_eq!(stats.avg_hit(), 400);
+        assert_eq!(stats.hit_list, vec![300, 800, 100]);
+
+        let empty = DamageStats::from_records([].iter());
+        assert_eq!(empty, DamageStats::default());
+        assert_eq!(empty.avg_hit(), 0);
+    }
+
+    #[test]
+    fn test_quest_damage_stats_summary() {
+        let start = Utc::now();
+        let mut stats = QuestDamageStats::new(start);
+        let hit = |damage: i32, target: i32, id: i32| DamageData {
+            fsm: Fsm { target, id },
+            ..damage_data(damage, false)
+        };
+        stats.record(&hit(100, 3, 1));
+        stats.record(&hit(500, 3, 2));
+        stats.record(&hit(300, 3, 3));
+        stats.record(&hit(250, 3, 4));
+        stats.record(&hit(200, 3, 1));
+        stats.finish(start + TimeDelta::try_seconds(10).unwrap());
+        // 任务完成后不再统计
+        stats.record(&hit(9999, 3, 1));
+
+        let move_names = HashMap::from([(Fsm { target: 3, id: 2 }, "强击".to_string())]);
+        let summary = stats.summary(&move_names, start + TimeDelta::try_seconds(60).unwrap());
+        assert_eq!(summary.total_damage, 1350);
+        assert_eq!(summary.hits, 5);
+        assert_eq!(summary.dps as i64, 135);
+        assert_eq!(summary.max_hit, 500);
+        assert_eq!(summary.max_hit_move, "强击");
+        assert_eq!(summary.monsters, vec![1350]);
+        assert_eq!(
+            summary.top_moves,
+            vec![
+                ("强击".to_string(), 500),
+                ("3:1".to_string(), 300),
+                ("3:3".to_string(), 300)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_quest_damage_stats_monster_despawn() {
+        let mut stats = QuestDamageStats::new(Utc::now());
+        let hit = |damage: i32, target: usize| DamageData {
+            target,
+            ..damage_data(damage, false)
+        };
+        stats.record(&hit(100, 0x1000));
+        stats.record(&hit(200, 0x2000));
+        stats.record(&hit(300, 0x1000));
+        assert_eq!(stats.monster_damage(0x1000), Some((1, 400)));
+        // 地址被新的怪物复用
+        stats.despawn_monster(0x1000);
+        assert_eq!(stats.monster_damage(0x1000), Some((1, 400)));
+        stats.record(&hit(50, 0x1000));
+        assert_eq!(stats.monster_damage(0x1000), Some((3, 50)));
+
+        let summary = stats.summary(&HashMap::new(), Utc::now());
+        assert_eq!(summary.monsters, vec![400, 200, 50]);
+    }
 }
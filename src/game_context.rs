@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use mhw_toolkit::game::resources::WeaponType;
 
 use crate::game;
@@ -10,13 +12,41 @@ pub struct Context {
     pub plugin_enabled: bool,
     pub chat_command: Option<ChatCommand>,
     pub quest_state: i32,
+    /// 本次任务的猫车次数
+    pub faint_count: i32,
+    /// 当前体力
+    pub health: f32,
+    /// 最大体力
+    pub max_health: f32,
+    /// 可恢复体力（红血）
+    pub red_health: f32,
+    /// 耐力
+    pub stamina: f32,
     pub longsword_level: i32,
+    /// 太刀气刃槽和开刃等级时间
+    pub longsword: Longsword,
     pub weapon_type: WeaponType,
+    /// 大剑和大锤的蓄力等级
+    pub charge_level: i32,
     pub fsm: Fsm,
     pub use_item_id: i32,
     pub insect_glaive: InsectGlaive,
     pub charge_blade: ChargeBlade,
+    pub switch_axe: SwitchAxe,
+    pub gunlance: Gunlance,
+    pub hunting_horn: HuntingHorn,
+    /// 弓和弩的弹药、瓶和蓄力（仅远程武器）
+    pub ranged: RangedWeapon,
+    pub dual_blades: DualBlades,
+    /// 锋利度（仅近战武器）
+    pub sharpness: Sharpness,
     pub specialized_tool: Option<SpecializedTool>,
+    /// 当前追踪的大型怪物
+    pub monsters: Vec<Monster>,
+    /// 增益和异常状态的剩余时间，键为状态名称
+    pub status_effects: HashMap<String, f32>,
+    /// 生效中的猫饭技能ID
+    pub food_skills: Vec<i32>,
 
     pub last_ctx: Option<Box<Context>>,
 }
@@ -27,6 +57,12 @@ impl Context {
 
         self.chat_command = game::get_chat_command();
         self.quest_state = game::get_quest_state();
+        self.faint_count = game::get_faint_count();
+        let vitals = game::get_player_vitals();
+        self.health = vitals.health;
+        self.max_health = vitals.max_health;
+        self.red_health = vitals.red_health;
+        self.stamina = vitals.stamina;
         self.weapon_type = game::get_weapon_type().unwrap_or(WeaponType::GreatSowrd);
         self.fsm = game::get_fsm();
         self.use_item_id = game::get_use_item_id();
@@ -35,6 +71,21 @@ impl Context {
         } else {
             0
         };
+        self.longsword = if WeaponType::LongSword == self.weapon_type {
+            game::get_longsword_data().unwrap_or_default()
+        } else {
+            Longsword::default()
+        };
+        self.charge_level = if is_charge_weapon(&self.weapon_type) {
+            game::get_charge_level().unwrap_or(0)
+        } else {
+            0
+        };
+        self.dual_blades = if WeaponType::DualBlades == self.weapon_type {
+            game::get_dual_blades_data().unwrap_or_default()
+        } else {
+            DualBlades::default()
+        };
         self.insect_glaive = if WeaponType::InsectGlaive == self.weapon_type {
             game::get_insect_glaive_data().unwrap_or_default()
         } else {
@@ -45,7 +96,56 @@ impl Context {
         } else {
             ChargeBlade::default()
         };
+        self.switch_axe = if WeaponType::SwitchAxe == self.weapon_type {
+            game::get_switch_axe_data().unwrap_or_default()
+        } else {
+            SwitchAxe::default()
+        };
+        self.gunlance = if WeaponType::Gunlance == self.weapon_type {
+            game::get_gunlance_data().unwrap_or_default()
+        } else {
+            Gunlance::default()
+        };
+        self.hunting_horn = if WeaponType::HuntingHorn == self.weapon_type {
+            game::get_hunting_horn_data().unwrap_or_default()
+        } else {
+            HuntingHorn::default()
+        };
+        self.ranged = if is_ranged_weapon(&self.weapon_type) {
+            let mut ranged = game::get_ranged_weapon_data().unwrap_or_default();
+            // 弓没有携带数量，弩没有蓄力等级
+            if WeaponType::Bow == self.weapon_type {
+                ranged.reserve = 0;
+            } else {
+                ranged.charge_level = 0;
+            }
+            ranged
+        } else {
+            RangedWeapon::default()
+        };
+        self.sharpness = if is_ranged_weapon(&self.weapon_type) {
+            Sharpness::default()
+        } else {
+            game::get_sharpness().unwrap_or_default()
+        };
         self.specialized_tool = game::get_specialized_tool();
+        self.monsters = game::get_monsters();
+        self.status_effects = game::get_status_effects();
+        self.food_skills = game::get_food_skills();
+    }
+
+    /// 增益、异常状态或猫饭技能是否生效中
+    pub fn is_status_active(&self, name: &str) -> bool {
+        self.status_effects.get(name).is_some_and(|timer| *timer > 0.0)
+            || find_food_skill(name).is_some_and(|def| self.food_skills.contains(&def.id))
+    }
+
+    /// 体力百分比（0-100）
+    pub fn health_percent(&self) -> i32 {
+        if self.max_health <= 0.0 {
+            return 0;
+        }
+        (self.health / self.max_health * 100.0) as i32
     }
 
     pub fn store_last_context(&mut self) {
@@ -60,18 +160,44 @@ impl Default for Context {
             plugin_enabled: true,
             chat_command: Default::default(),
             quest_state: Default::default(),
+            faint_count: Default::default(),
+            health: Default::default(),
+            max_health: Default::default(),
+            red_health: Default::default(),
+            stamina: Default::default(),
             longsword_level: Default::default(),
+            longsword: Default::default(),
             weapon_type: WeaponType::GreatSowrd,
+            charge_level: Default::default(),
             fsm: Default::default(),
             use_item_id: Default::default(),
             insect_glaive: Default::default(),
             charge_blade: Default::default(),
+            switch_axe: Default::default(),
+            gunlance: Default::default(),
+            hunting_horn: Default::default(),
+            ranged: Default::default(),
+            dual_blades: Default::default(),
+            sharpness: Default::default(),
             specialized_tool: Default::default(),
+            monsters: Default::default(),
+            status_effects: Default::default(),
+            food_skills: Default::default(),
             last_ctx: Default::default(),
         }
     }
 }
 
+/// 是否为远程武器
+pub fn is_ranged_weapon(weapon_type: &WeaponType) -> bool {
+    matches!(weapon_type, WeaponType::Bow | WeaponType::HeavyBowgun | WeaponType::LightBowgun)
+}
+
+/// 是否为可蓄力的武器（大剑和大锤）
+pub fn is_charge_weapon(weapon_type: &WeaponType) -> bool {
+    matches!(weapon_type, WeaponType::GreatSowrd | WeaponType::Hammer)
+}
+
 /// 动作
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
 pub struct Fsm {
@@ -97,6 +223,32 @@ impl ChatCommand {
     }
 }
 
+/// 太刀
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Longsword {
+    /// 气刃槽（0-100）
+    pub spirit_gauge: f32,
+    /// 当前开刃等级剩余时间
+    pub level_timer: f32,
+}
+
+impl Longsword {
+    pub fn is_gauge_full(&self) -> bool {
+        self.spirit_gauge >= 100.0
+    }
+}
+
+/// 双刀
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DualBlades {
+    /// 是否处于鬼人化
+    pub demon_mode: bool,
+    /// 鬼人槽（0-100）
+    pub archdemon_gauge: f32,
+    /// 鬼人化时每秒消耗的耐力
+    pub stamina_drain: f32,
+}
+
 /// 操虫棍
 #[derive(Clone, Debug, Default, PartialEq)]
 pub struct InsectGlaive {
@@ -136,6 +288,577 @@ impl ChargeBlade {
     }
 }
 
-/// 特殊装备
+/// 玩家体力和耐力
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PlayerVitals {
+    pub health: f32,
+    pub max_health: f32,
+    pub red_health: f32,
+    pub stamina: f32,
+}
+
+/// 斩击斧
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SwitchAxe {
+    /// 剑槽（0-100）
+    pub switch_gauge: f32,
+    /// 觉醒槽（0-100）
+    pub amp_gauge: f32,
+    /// 剑强化（觉醒）剩余时间
+    pub amped_timer: f32,
+    /// 斧强化剩余时间
+    pub power_axe_timer: f32,
+}
+
+impl SwitchAxe {
+    pub fn is_amped(&self) -> bool {
+        self.amped_timer > 0.0
+    }
+
+    pub fn is_power_axe(&self) -> bool {
+        self.power_axe_timer > 0.0
+    }
+}
+
+/// 铳枪
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Gunlance {
+    /// 弹药数量
+    pub shells: i32,
+    /// 最大弹药数量
+    pub max_shells: i32,
+    /// 是否装填龙杭弹
+    pub stake_loaded: bool,
+    /// 龙击炮剩余冷却时间
+    pub wyvernfire_cooldown: f32,
+    /// 是否装填龙杭炮
+    pub wyrmstake_loaded: bool,
+}
+
+impl Gunlance {
+    pub fn is_wyvernfire_ready(&self) -> bool {
+        self.wyvernfire_cooldown <= 0.0
+    }
+}
+
+/// 狩猎笛
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct HuntingHorn {
+    /// 已输入的音符，按输入顺序排列
+    pub notes: Vec<i32>,
+    /// 旋律槽中的旋律ID，按演奏顺序排列
+    pub song_queue: Vec<i32>,
+}
+
+impl HuntingHorn {
+    /// 旋律槽容量
+    pub const MAX_SONG_QUEUE: usize = 3;
+
+    pub fn is_queue_full(&self) -> bool {
+        self.song_queue.len() >= Self::MAX_SONG_QUEUE
+    }
+
+    /// 根据旋律槽的变化推断本次演奏的旋律
+    ///
+    /// 演奏会从旋律槽头部移除旋律，其余旋律顺序不变。
+    /// 切换武器或任务状态变化时旋律槽同样会被清空，需由调用方排除
+    pub fn performed_songs<'a>(&self, old: &'a HuntingHorn) -> &'a [i32] {
+        let new_len = self.song_queue.len();
+        if new_len >= old.song_queue.len() {
+            return &[];
+        }
+        let performed = old.song_queue.len() - new_len;
+        if old.song_queue[performed..] == self.song_queue[..] {
+            &old.song_queue[..performed]
+        } else {
+            &[]
+        }
+    }
+}
+
+/// 狩猎笛旋律定义
+pub struct SongDef {
+    pub id: i32,
+    /// 旋律名称，用于配置文件
+    pub name: &'static str,
+    /// 旋律显示名称
+    pub display_name: &'static str,
+}
+
+/// 狩猎笛旋律
+pub const HUNTING_HORN_SONGS: &[SongDef] = &[
+    SongDef {
+        id: 0,
+        name: "self_improvement",
+        display_name: "自我强化",
+    },
+    SongDef {
+        id: 1,
+        name: "attack_up",
+        display_name: "攻击力强化",
+    },
+    SongDef {
+        id: 2,
+        name: "defense_up",
+        display_name: "防御力强化",
+    },
+    SongDef {
+        id: 3,
+        name: "health_recovery",
+        display_name: "体力回复",
+    },
+    SongDef {
+        id: 4,
+        name: "stamina_use_reduced",
+        display_name: "耐力减轻",
+    },
+    SongDef {
+        id: 5,
+        name: "earplugs",
+        display_name: "听觉保护",
+    },
+    SongDef {
+        id: 6,
+        name: "tremors_negated",
+        display_name: "耐震",
+    },
+    SongDef {
+        id: 7,
+        name: "wind_pressure_negated",
+        display_name: "风压无效",
+    },
+    SongDef {
+        id: 8,
+        name: "elemental_attack_boost",
+        display_name: "属性攻击力强化",
+    },
+    SongDef {
+        id: 9,
+        name: "affinity_up",
+        display_name: "会心率提升",
+    },
+    SongDef {
+        id: 10,
+        name: "sharpness_loss_reduced",
+        display_name: "锋利度减缓",
+    },
+    SongDef {
+        id: 11,
+        name: "divine_protection",
+        display_name: "净化",
+    },
+    SongDef {
+        id: 12,
+        name: "knockbacks_negated",
+        display_name: "击退无效",
+    },
+    SongDef {
+        id: 13,
+        name: "sonic_waves",
+        display_name: "音波攻击",
+    },
+    SongDef {
+        id: 14,
+        name: "infernal_melody",
+        display_name: "炎熔旋律",
+    },
+];
+
+/// 根据名称查找旋律定义
+pub fn find_song(name: &str) -> Option<&'static SongDef> {
+    HUNTING_HORN_SONGS.iter().find(|def| def.name == name)
+}
+
+/// 根据ID查找旋律定义
+pub fn find_song_by_id(id: i32) -> Option<&'static SongDef> {
+    HUNTING_HORN_SONGS.iter().find(|def| def.id == id)
+}
+
+/// 弓和弩
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RangedWeapon {
+    /// 当前弹药类型ID，弓为瓶类型ID
+    pub ammo_type: i32,
+    /// 弩已装填的弹数，弓为当前瓶的剩余数量
+    pub loaded: i32,
+    /// 弩当前弹药的剩余携带数量，弓固定为 0
+    pub reserve: i32,
+    /// 弓的蓄力等级，弩固定为 0
+    pub charge_level: i32,
+}
+
+/// 弹药或瓶定义
+pub struct AmmoDef {
+    pub id: i32,
+    /// 弹药名称，用于配置文件
+    pub name: &'static str,
+    /// 弹药显示名称
+    pub display_name: &'static str,
+    /// 是否为弓的瓶
+    pub is_coating: bool,
+}
+
+const fn ammo(id: i32, name: &'static str, display_name: &'static str) -> AmmoDef {
+    AmmoDef {
+        id,
+        name,
+        display_name,
+        is_coating: false,
+    }
+}
+
+const fn coating(id: i32, name: &'static str, display_name: &'static str) -> AmmoDef {
+    AmmoDef {
+        id,
+        name,
+        display_name,
+        is_coating: true,
+    }
+}
+
+/// 弩的弹药和弓的瓶
+pub const AMMO_TYPES: &[AmmoDef] = &[
+    // 弩
+    ammo(0, "normal_1", "通常弹Lv1"),
+    ammo(1, "normal_2", "通常弹Lv2"),
+    ammo(2, "normal_3", "通常弹Lv3"),
+    ammo(3, "pierce_1", "贯通弹Lv1"),
+    ammo(4, "pierce_2", "贯通弹Lv2"),
+    ammo(5, "pierce_3", "贯通弹Lv3"),
+    ammo(6, "spread_1", "散弹Lv1"),
+    ammo(7, "spread_2", "散弹Lv2"),
+    ammo(8, "spread_3", "散弹Lv3"),
+    ammo(9, "sticky_1", "榴弹Lv1"),
+    ammo(10, "sticky_2", "榴弹Lv2"),
+    ammo(11, "sticky_3", "榴弹Lv3"),
+    ammo(12, "cluster_1", "扩散弹Lv1"),
+    ammo(13, "cluster_2", "扩散弹Lv2"),
+    ammo(14, "cluster_3", "扩散弹Lv3"),
+    ammo(15, "wyvern", "龙击弹"),
+    ammo(16, "slicing", "斩裂弹"),
+    ammo(17, "flaming", "火炎弹"),
+    ammo(18, "water", "水冷弹"),
+    ammo(19, "freeze", "冰结弹"),
+    ammo(20, "thunder", "电击弹"),
+    ammo(21, "dragon", "灭龙弹"),
+    ammo(22, "poison_1", "毒弹Lv1"),
+    ammo(23, "poison_2", "毒弹Lv2"),
+    ammo(24, "paralysis_1", "麻痹弹Lv1"),
+    ammo(25, "paralysis_2", "麻痹弹Lv2"),
+    ammo(26, "sleep_1", "睡眠弹Lv1"),
+    ammo(27, "sleep_2", "睡眠弹Lv2"),
+    ammo(28, "exhaust_1", "减气弹Lv1"),
+    ammo(29, "exhaust_2", "减气弹Lv2"),
+    ammo(30, "recover_1", "回复弹Lv1"),
+    ammo(31, "recover_2", "回复弹Lv2"),
+    ammo(32, "demon", "鬼人弹"),
+    ammo(33, "armor", "硬化弹"),
+    ammo(34, "tranq", "捕获用麻醉弹"),
+    // 弓
+    coating(0, "close_range_coating", "接击瓶"),
+    coating(1, "power_coating", "强击瓶"),
+    coating(2, "poison_coating", "毒瓶"),
+    coating(3, "paralysis_coating", "麻痹瓶"),
+    coating(4, "sleep_coating", "睡眠瓶"),
+    coating(5, "blast_coating", "爆破瓶"),
+    coating(6, "exhaust_coating", "减气瓶"),
+];
+
+/// 根据名称查找弹药或瓶定义
+pub fn find_ammo(name: &str) -> Option<&'static AmmoDef> {
+    AMMO_TYPES.iter().find(|def| def.name == name)
+}
+
+/// 根据ID查找弹药或瓶定义
+pub fn find_ammo_by_id(id: i32, is_coating: bool) -> Option<&'static AmmoDef> {
+    AMMO_TYPES.iter().find(|def| def.id == id && def.is_coating == is_coating)
+}
+
+/// 锋利度
 #[derive(Clone, Debug, Default, PartialEq)]
-pub struct SpecializedTool {}
+pub struct Sharpness {
+    /// 颜色等级
+    /// 0 红，1 橙，2 黄，3 绿，4 蓝，5 白，6 紫
+    pub color: i32,
+    /// 当前颜色剩余锋利度
+    pub remaining: i32,
+}
+
+impl Sharpness {
+    /// 锋利度颜色名称，按颜色等级排列
+    pub const COLOR_NAMES: [&'static str; 7] = ["red", "orange", "yellow", "green", "blue", "white", "purple"];
+
+    /// 根据颜色名称获取颜色等级
+    pub fn color_from_name(name: &str) -> Option<i32> {
+        Self::COLOR_NAMES.iter().position(|n| *n == name).map(|idx| idx as i32)
+    }
+
+    /// 颜色等级对应的中文名称
+    pub fn color_display_name(&self) -> &'static str {
+        match self.color {
+            0 => "红",
+            1 => "橙",
+            2 => "黄",
+            3 => "绿",
+            4 => "蓝",
+            5 => "白",
+            6 => "紫",
+            _ => "未知",
+        }
+    }
+}
+
+/// 增益或异常状态定义
+pub struct StatusEffectDef {
+    /// 状态名称
+    pub name: &'static str,
+    /// 相对玩家状态数据的偏移
+    pub offset: isize,
+    /// 是否为异常状态
+    pub is_ailment: bool,
+}
+
+/// 可读取的增益和异常状态
+pub const STATUS_EFFECTS: &[StatusEffectDef] = &[
+    // 增益
+    StatusEffectDef {
+        name: "might_seed",
+        offset: 0x6C4,
+        is_ailment: false,
+    },
+    StatusEffectDef {
+        name: "might_pill",
+        offset: 0x6CC,
+        is_ailment: false,
+    },
+    StatusEffectDef {
+        name: "demondrug",
+        offset: 0x6B8,
+        is_ailment: false,
+    },
+    StatusEffectDef {
+        name: "mega_demondrug",
+        offset: 0x6BC,
+        is_ailment: false,
+    },
+    StatusEffectDef {
+        name: "armorskin",
+        offset: 0x6C0,
+        is_ailment: false,
+    },
+    StatusEffectDef {
+        name: "mega_armorskin",
+        offset: 0x6C8,
+        is_ailment: false,
+    },
+    StatusEffectDef {
+        name: "adamant_seed",
+        offset: 0x6D0,
+        is_ailment: false,
+    },
+    StatusEffectDef {
+        name: "adamant_pill",
+        offset: 0x6D8,
+        is_ailment: false,
+    },
+    StatusEffectDef {
+        name: "dash_juice",
+        offset: 0x6E4,
+        is_ailment: false,
+    },
+    // 异常状态
+    StatusEffectDef {
+        name: "poison",
+        offset: 0x5DC,
+        is_ailment: true,
+    },
+    StatusEffectDef {
+        name: "venom",
+        offset: 0x5E0,
+        is_ailment: true,
+    },
+    StatusEffectDef {
+        name: "paralysis",
+        offset: 0x5F8,
+        is_ailment: true,
+    },
+    StatusEffectDef {
+        name: "sleep",
+        offset: 0x5F0,
+        is_ailment: true,
+    },
+    StatusEffectDef {
+        name: "stun",
+        offset: 0x604,
+        is_ailment: true,
+    },
+    StatusEffectDef {
+        name: "fireblight",
+        offset: 0x610,
+        is_ailment: true,
+    },
+    StatusEffectDef {
+        name: "waterblight",
+        offset: 0x618,
+        is_ailment: true,
+    },
+    StatusEffectDef {
+        name: "thunderblight",
+        offset: 0x614,
+        is_ailment: true,
+    },
+    StatusEffectDef {
+        name: "iceblight",
+        offset: 0x61C,
+        is_ailment: true,
+    },
+    StatusEffectDef {
+        name: "bleeding",
+        offset: 0x628,
+        is_ailment: true,
+    },
+    StatusEffectDef {
+        name: "defense_down",
+        offset: 0x630,
+        is_ailment: true,
+    },
+];
+
+/// 查找增益或异常状态定义
+pub fn find_status_effect(name: &str) -> Option<&'static StatusEffectDef> {
+    STATUS_EFFECTS.iter().find(|def| def.name == name)
+}
+
+/// 猫饭技能定义
+pub struct FoodSkillDef {
+    pub id: i32,
+    /// 技能名称，用于配置文件
+    pub name: &'static str,
+    /// 技能显示名称
+    pub display_name: &'static str,
+}
+
+const fn food_skill(id: i32, name: &'static str, display_name: &'static str) -> FoodSkillDef {
+    FoodSkillDef { id, name, display_name }
+}
+
+/// 可读取的猫饭技能
+pub const FOOD_SKILLS: &[FoodSkillDef] = &[
+    food_skill(0, "felyne_insurance", "猫之报酬金保险"),
+    food_skill(1, "felyne_moxie", "猫之不屈"),
+    food_skill(2, "felyne_heroics", "猫之火事场力"),
+    food_skill(3, "felyne_polisher", "猫之研磨术"),
+    food_skill(4, "felyne_slugger", "猫之KO术"),
+    food_skill(5, "felyne_temper", "猫之防御术"),
+    food_skill(6, "felyne_bombardier", "猫之炮术"),
+    food_skill(7, "felyne_specialist", "猫之特殊攻击术"),
+    food_skill(8, "felyne_feathercat", "猫之体术"),
+    food_skill(9, "felyne_medic", "猫之医疗术"),
+    food_skill(10, "felyne_carver", "猫之剥取术"),
+    food_skill(11, "felyne_rider", "猫之骑乘术"),
+    food_skill(12, "felyne_sprinter", "猫之冲刺术"),
+    food_skill(13, "felyne_foodie", "猫之贪吃"),
+];
+
+/// 查找猫饭技能定义
+pub fn find_food_skill(name: &str) -> Option<&'static FoodSkillDef> {
+    FOOD_SKILLS.iter().find(|def| def.name == name)
+}
+
+/// 根据ID查找猫饭技能定义
+pub fn find_food_skill_by_id(id: i32) -> Option<&'static FoodSkillDef> {
+    FOOD_SKILLS.iter().find(|def| def.id == id)
+}
+
+/// 特殊装备（衣装）
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SpecializedTool {
+    /// 装备的两件衣装
+    pub slots: [Mantle; 2],
+}
+
+/// 衣装
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Mantle {
+    /// 衣装ID，未装备时为 -1
+    pub id: i32,
+    /// 剩余效果时间
+    pub active_timer: f32,
+    /// 剩余冷却时间
+    pub cooldown_timer: f32,
+}
+
+impl Mantle {
+    pub fn is_equipped(&self) -> bool {
+        self.id >= 0
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.active_timer > 0.0
+    }
+
+    pub fn is_ready(&self) -> bool {
+        !self.is_active() && self.cooldown_timer <= 0.0
+    }
+}
+
+/// 怪物
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Monster {
+    /// 怪物地址
+    pub address: usize,
+    pub monster_id: i32,
+    /// 当前体力
+    pub health: f32,
+    /// 最大体力
+    pub max_health: f32,
+    /// 是否愤怒
+    pub is_enraged: bool,
+    /// 是否疲劳
+    pub is_exhausted: bool,
+    /// 是否被陷阱困住
+    pub is_trapped: bool,
+}
+
+impl Monster {
+    /// 体力百分比（0-100）
+    pub fn health_percent(&self) -> i32 {
+        if self.max_health <= 0.0 {
+            return 0;
+        }
+        (self.health / self.max_health * 100.0) as i32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_status_effect() {
+        assert!(!find_status_effect("might_pill").unwrap().is_ailment);
+        assert!(find_status_effect("poison").unwrap().is_ailment);
+        assert!(find_status_effect("unknown").is_none());
+        assert!(find_food_skill("felyne_insurance").is_some());
+        // 名称不能重复，猫饭技能与增益和异常状态共用名称空间
+        let names: Vec<&str> =
+            STATUS_EFFECTS.iter().map(|def| def.name).chain(FOOD_SKILLS.iter().map(|def| def.name)).collect();
+        for (i, name) in names.iter().enumerate() {
+            assert!(!names[i + 1..].contains(name));
+        }
+    }
+
+    fn hunting_horn(song_queue: &[i32]) -> HuntingHorn {
+        HuntingHorn {
+            notes: Vec::new(),
+            song_queue: song_queue.to_vec(),
+        }
+    }
+
+    #[test]
+    fn test_performed_songs() {
+        assert_eq!(hunting_horn(&[3]).performed_songs(&hunting_horn(&[1, 2, 3])), &[1, 2]);
+        assert!(hunting_horn(&[1, 2]).performed_songs(&hunting_horn(&[1])).is_empty());
+        assert!(hunting_horn(&[5]).performed_songs(&hunting_horn(&[1, 2])).is_empty());
+    }
+}
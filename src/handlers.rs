@@ -1,9 +1,13 @@
 use crate::{
+    chat_queue::ChatQueue,
     configs,
     event::Event,
-    game::DamageCollector,
-    game_context::{ChargeBlade, ChatCommand, Context, InsectGlaive},
-    triggers::{self, SharedContext, Trigger},
+    game::{self, DamageCollector},
+    game_context::{
+        self, is_charge_weapon, is_ranged_weapon, ChargeBlade, ChatCommand, Context, DualBlades, Gunlance,
+        InsectGlaive, Longsword, Mantle, Monster, SwitchAxe,
+    },
+    triggers::{self, CoolDownGroups, SharedContext, Trigger},
     tx_send_or_break, TriggerManager,
 };
 
@@ -22,7 +26,7 @@ use tokio::sync::{
 /// 事件监听器
 pub async fn event_listener(tx: Sender<Event>) {
     let mut ctx = Context::default();
-    loop {
+    'listener: loop {
         // 每秒20次事件检查
         tokio::time::sleep(Duration::from_millis(50)).await;
         // 更新上下文
@@ -91,6 +95,49 @@ pub async fn event_listener(tx: Sender<Event>) {
                 old: last_ctx.quest_state,
             }));
         }
+        // 任务中猫车次数增加
+        if ctx.quest_state == 2 && ctx.faint_count > last_ctx.faint_count {
+            debug!("on {} faint_count = {}", "Event::PlayerFainted", ctx.faint_count);
+            tx_send_or_break!(tx.send(Event::PlayerFainted {
+                faint_count: ctx.faint_count,
+            }));
+        }
+        if ctx.quest_state == 2 && is_player_vitals_changed(&ctx, last_ctx) {
+            debug!("on {}", "Event::PlayerVitals");
+            tx_send_or_break!(tx.send(Event::PlayerVitals));
+        }
+        if let (Some(new), Some(old)) = (&ctx.specialized_tool, &last_ctx.specialized_tool) {
+            if new.slots.iter().zip(old.slots.iter()).any(|(new, old)| is_mantle_changed(new, old)) {
+                debug!("on {}", "Event::SpecializedTool");
+                tx_send_or_break!(tx.send(Event::SpecializedTool));
+            }
+        }
+        for def in game_context::STATUS_EFFECTS.iter() {
+            let active = ctx.is_status_active(def.name);
+            if active != last_ctx.is_status_active(def.name) {
+                debug!("on {} {} active = {}", "Event::StatusEffectChanged", def.name, active);
+                tx_send_or_break!(
+                    tx.send(Event::StatusEffectChanged {
+                        name: def.name.to_string(),
+                        active,
+                    }),
+                    'listener
+                );
+            }
+        }
+        for def in game_context::FOOD_SKILLS.iter() {
+            let active = ctx.food_skills.contains(&def.id);
+            if active != last_ctx.food_skills.contains(&def.id) {
+                debug!("on {} {} active = {}", "Event::StatusEffectChanged", def.name, active);
+                tx_send_or_break!(
+                    tx.send(Event::StatusEffectChanged {
+                        name: def.name.to_string(),
+                        active,
+                    }),
+                    'listener
+                );
+            }
+        }
         if ctx.weapon_type != last_ctx.weapon_type {
             debug!("on {} from {:?} to {:?}", "Event::WeaponTypeChanged", last_ctx.weapon_type, ctx.weapon_type);
             tx_send_or_break!(tx.send(Event::WeaponTypeChanged {
@@ -111,6 +158,19 @@ pub async fn event_listener(tx: Sender<Event>) {
                 item_id: ctx.use_item_id,
             }));
         }
+        for new in ctx.monsters.iter() {
+            let Some(old) = last_ctx.monsters.iter().find(|m| m.address == new.address) else {
+                continue;
+            };
+            if is_monster_state_changed(new, old) {
+                debug!("on {} monster = {:#x}", "Event::MonsterStateChanged", new.address);
+                tx_send_or_break!(tx.send(Event::MonsterStateChanged { monster: new.address }), 'listener);
+            }
+        }
+        if ctx.sharpness != last_ctx.sharpness && !is_ranged_weapon(&ctx.weapon_type) {
+            debug!("on {} {:?}", "Event::SharpnessChanged", ctx.sharpness);
+            tx_send_or_break!(tx.send(Event::SharpnessChanged));
+        }
         if WeaponType::LongSword == ctx.weapon_type {
             if ctx.longsword_level != last_ctx.longsword_level {
                 debug!(
@@ -122,6 +182,13 @@ pub async fn event_listener(tx: Sender<Event>) {
                     old: last_ctx.longsword_level,
                 }));
             }
+            if is_longsword_changed(&ctx.longsword, &last_ctx.longsword) {
+                debug!("on {} {:?}", "Event::LongswordSpiritChanged", ctx.longsword);
+                tx_send_or_break!(tx.send(Event::LongswordSpiritChanged {
+                    new: ctx.longsword.clone(),
+                    old: last_ctx.longsword.clone(),
+                }));
+            }
         } else if WeaponType::InsectGlaive == ctx.weapon_type {
             let new = &ctx.insect_glaive;
             let old = &last_ctx.insect_glaive;
@@ -136,12 +203,80 @@ pub async fn event_listener(tx: Sender<Event>) {
                 debug!("on {}", "Event::ChargeBlade",);
                 tx_send_or_break!(tx.send(Event::ChargeBlade));
             }
+        } else if WeaponType::SwitchAxe == ctx.weapon_type {
+            let new = &ctx.switch_axe;
+            let old = &last_ctx.switch_axe;
+            if is_switch_axe_changed(new, old) {
+                debug!("on {}", "Event::SwitchAxe",);
+                tx_send_or_break!(tx.send(Event::SwitchAxe));
+            }
+        } else if WeaponType::Gunlance == ctx.weapon_type {
+            let new = &ctx.gunlance;
+            let old = &last_ctx.gunlance;
+            if is_gunlance_changed(new, old) {
+                debug!("on {}", "Event::Gunlance",);
+                tx_send_or_break!(tx.send(Event::Gunlance));
+            }
+        }
+        if WeaponType::HuntingHorn == ctx.weapon_type {
+            let new = &ctx.hunting_horn;
+            let old = &last_ctx.hunting_horn;
+            // 切换武器或任务状态变化时旋律槽会被清空，不视为演奏
+            let performed = if ctx.weapon_type == last_ctx.weapon_type && ctx.quest_state == last_ctx.quest_state {
+                new.performed_songs(old)
+            } else {
+                &[]
+            };
+            for song_id in performed {
+                debug!("on {} song_id = {}", "Event::HuntingHornSongPerformed", song_id);
+                tx_send_or_break!(tx.send(Event::HuntingHornSongPerformed { song_id: *song_id }), 'listener);
+            }
+            if new.song_queue != old.song_queue {
+                debug!("on {} {:?}", "Event::HuntingHornQueueChanged", new.song_queue);
+                tx_send_or_break!(tx.send(Event::HuntingHornQueueChanged));
+            }
+        }
+        if is_ranged_weapon(&ctx.weapon_type) {
+            if ctx.ranged != last_ctx.ranged {
+                debug!("on {} {:?}", "Event::RangedWeapon", ctx.ranged);
+                tx_send_or_break!(tx.send(Event::RangedWeapon));
+            }
+        }
+        if WeaponType::DualBlades == ctx.weapon_type {
+            let new = &ctx.dual_blades;
+            let old = &last_ctx.dual_blades;
+            if is_dual_blades_changed(new, old) {
+                debug!("on {}", "Event::DualBlades",);
+                tx_send_or_break!(tx.send(Event::DualBlades));
+            }
+        }
+        if is_charge_weapon(&ctx.weapon_type) {
+            if ctx.charge_level != last_ctx.charge_level {
+                debug!("on {} from {} to {}", "Event::ChargeLevelChanged", last_ctx.charge_level, ctx.charge_level);
+                tx_send_or_break!(tx.send(Event::ChargeLevelChanged {
+                    new: ctx.charge_level,
+                    old: last_ctx.charge_level,
+                }));
+            }
         }
     }
 
     error!("主事件发送端已终止");
 }
 
+fn is_switch_axe_changed(new: &SwitchAxe, old: &SwitchAxe) -> bool {
+    (new.amped_timer <= 0.0 && old.amped_timer > 0.0)
+        || (new.amped_timer > 0.0 && old.amped_timer <= 0.0)
+        || (new.power_axe_timer <= 0.0 && old.power_axe_timer > 0.0)
+        || (new.power_axe_timer > 0.0 && old.power_axe_timer <= 0.0)
+        || (new.switch_gauge as i32 != old.switch_gauge as i32)
+        || (new.amp_gauge as i32 != old.amp_gauge as i32)
+}
+
+fn is_longsword_changed(new: &Longsword, old: &Longsword) -> bool {
+    new.spirit_gauge as i32 != old.spirit_gauge as i32 || new.level_timer as i32 != old.level_timer as i32
+}
+
 fn is_insect_glaive_changed(new: &InsectGlaive, old: &InsectGlaive) -> bool {
     (new.attack_timer <= 0.0 && old.attack_timer > 0.0)
         || (new.attack_timer > 0.0 && old.attack_timer <= 0.0)
@@ -163,23 +298,81 @@ fn is_charge_blade_changed(new: &ChargeBlade, old: &ChargeBlade) -> bool {
         || (new.sword_power != old.sword_power)
 }
 
+fn is_gunlance_changed(new: &Gunlance, old: &Gunlance) -> bool {
+    (new.shells != old.shells)
+        || (new.stake_loaded != old.stake_loaded)
+        || (new.wyrmstake_loaded != old.wyrmstake_loaded)
+        || (new.wyvernfire_cooldown <= 0.0 && old.wyvernfire_cooldown > 0.0)
+        || (new.wyvernfire_cooldown > 0.0 && old.wyvernfire_cooldown <= 0.0)
+        || (new.wyvernfire_cooldown as i32 != old.wyvernfire_cooldown as i32)
+}
+
+fn is_dual_blades_changed(new: &DualBlades, old: &DualBlades) -> bool {
+    (new.demon_mode != old.demon_mode) || (new.archdemon_gauge as i32 != old.archdemon_gauge as i32)
+}
+
+fn is_mantle_changed(new: &Mantle, old: &Mantle) -> bool {
+    new.id != old.id
+        || new.active_timer as i32 != old.active_timer as i32
+        || (new.active_timer > 0.0) != (old.active_timer > 0.0)
+        || (new.cooldown_timer > 0.0) != (old.cooldown_timer > 0.0)
+}
+
+fn is_player_vitals_changed(new: &Context, old: &Context) -> bool {
+    new.health as i32 != old.health as i32
+        || new.max_health as i32 != old.max_health as i32
+        || new.red_health as i32 != old.red_health as i32
+        || new.stamina as i32 != old.stamina as i32
+}
+
+fn is_monster_state_changed(new: &Monster, old: &Monster) -> bool {
+    new.health_percent() != old.health_percent()
+        || new.is_enraged != old.is_enraged
+        || new.is_exhausted != old.is_exhausted
+        || new.is_trapped != old.is_trapped
+}
+
 /// 事件处理器
 pub async fn event_handler(mut rx: Receiver<Event>) {
     let damage_collector = DamageCollector::instance();
     let mut trigger_mgr: Option<TriggerManager> = None;
     loop {
-        if let Some(e) = rx.recv().await {
+        if let Some(mut e) = rx.recv().await {
             if let Event::LoadTriggers { trigger_mgr: mgr } = e {
                 trigger_mgr = Some(mgr);
                 info!("已加载新的TriggerManager");
                 continue;
             }
-            if let Event::Damage { damage } = e {
-                damage_collector.on_damage(damage).await;
+            if let Event::Damage {
+                damage,
+                is_crit,
+                is_tenderized,
+                target,
+                ..
+            } = e
+            {
+                damage_collector.on_damage(damage, is_crit, is_tenderized, target).await;
             }
             if let Event::FsmChanged { new, .. } = e {
                 damage_collector.on_fsm_changed(&new).await;
             }
+            if let Event::QuestStateChanged { new, .. } = e {
+                damage_collector.on_quest_state_changed(new).await;
+            }
+            if let Event::MonsterSpawned {
+                monster, monster_id, ..
+            } = e
+            {
+                damage_collector.on_monster_spawned(monster, monster_id).await;
+            }
+            if let Event::MonsterDespawned { monster, .. } = e {
+                // 怪物ID以创建时的记录为准
+                let Some(monster_id) = damage_collector.on_monster_despawned(monster).await else {
+                    debug!("忽略未记录的怪物销毁：{:#x}", monster);
+                    continue;
+                };
+                e = Event::MonsterDespawned { monster, monster_id };
+            }
 
             if let Some(mgr) = &mut trigger_mgr {
                 if let Event::UpdateContext { ctx } = e {
@@ -204,10 +397,14 @@ pub async fn load_triggers() -> Result<TriggerManager, String> {
     };
     debug!("load config: {:?}", config);
     info!("已加载配置文件");
+    ChatQueue::instance().configure(&config.chat_queue).await;
+    DamageCollector::instance().configure(&config.damage).await;
+    DamageCollector::instance().set_move_names(&config.move_name).await;
     // 注册触发器
     let shared_ctx = Arc::new(RwLock::new(Context::default()));
-    let trigger_mgr = TriggerManager::new(shared_ctx.clone());
-    let triggers = parse_config(&config, shared_ctx.clone());
+    let cd_groups = CoolDownGroups::new(&config);
+    let triggers = parse_config(&config, shared_ctx.clone(), &cd_groups);
+    let trigger_mgr = TriggerManager::new(shared_ctx.clone(), cd_groups);
 
     let mgr_shared = Arc::new(Mutex::new(trigger_mgr));
     stream::iter(triggers.into_iter())
@@ -224,16 +421,19 @@ pub async fn load_triggers() -> Result<TriggerManager, String> {
     Ok(trigger_mgr)
 }
 
-pub fn parse_config(cfg: &configs::Config, shared_ctx: SharedContext) -> Vec<Trigger> {
+pub fn parse_config(cfg: &configs::Config, shared_ctx: SharedContext, cd_groups: &CoolDownGroups) -> Vec<Trigger> {
     cfg.trigger
         .iter()
         .map(move |t| {
             let shared_ctx_clone = Arc::clone(&shared_ctx);
-            triggers::register_trigger(t, shared_ctx_clone)
+            triggers::register_trigger(t, shared_ctx_clone, cd_groups)
         })
         .collect::<Vec<_>>()
 }
 
+/// 发送事件，失败时跳出所在循环
+///
+/// 在内层循环中使用时需指定外层循环的标签
 #[macro_export]
 macro_rules! tx_send_or_break {
     ( $tx:expr ) => {
@@ -242,6 +442,12 @@ macro_rules! tx_send_or_break {
             break;
         }
     };
+    ( $tx:expr, $label:lifetime ) => {
+        if let Err(e) = $tx.await {
+            error!("send event error: {}", e);
+            break $label;
+        }
+    };
 }
 
 #[cfg(test)]
@@ -261,4 +467,23 @@ mod tests {
         new.attack_timer = 2.0;
         assert!(!is_insect_glaive_changed(&new, &old));
     }
+
+    #[test]
+    fn test_is_monster_state_changed() {
+        let old = Monster {
+            health: 3100.0,
+            max_health: 10000.0,
+            ..Default::default()
+        };
+        let mut new = old.clone();
+        new.health = 3050.0;
+        assert!(!is_monster_state_changed(&new, &old));
+
+        new.health = 2990.0;
+        assert!(is_monster_state_changed(&new, &old));
+
+        new.health = old.health;
+        new.is_enraged = true;
+        assert!(is_monster_state_changed(&new, &old));
+    }
 }
@@ -26,7 +26,14 @@ extern "C" fn hook_function(
 ) {
     // 获取伤害值
     if let Some(sender) = HOOKS_SENDER.lock().unwrap().as_ref() {
-        if let Err(e) = sender.blocking_send(Event::Damage { damage }) {
+        let event = Event::Damage {
+            damage,
+            is_crit: is_crit != 0,
+            is_tenderized: is_tenderized != 0,
+            attack_id,
+            target: target as usize,
+        };
+        if let Err(e) = sender.blocking_send(event) {
             error!("发送伤害事件错误：{}", e);
         };
     }
@@ -39,9 +46,6 @@ extern "C" fn hook_function(
 
 pub fn install_hook() -> Result<(), String> {
     unsafe {
-        // 初始化MinHook
-        minhook_sys::MH_Initialize();
-
         // 获取目标函数地址
         let func_addr = AddressRepository::get_instance().lock().unwrap().get_address(address::player::DrawDamage)?;
         let target_function: *mut c_void = func_addr as *mut c_void;
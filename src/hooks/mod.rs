@@ -6,6 +6,7 @@ use tokio::sync::mpsc::{self, Receiver, Sender};
 use crate::event::Event;
 
 pub mod damage;
+pub mod monster;
 
 static HOOKS_SENDER: Mutex<Option<mpsc::Sender<Event>>> = Mutex::new(None);
 
@@ -13,17 +14,47 @@ pub fn install_hooks() -> Receiver<Event> {
     let (tx, rx) = mpsc::channel(256);
     HOOKS_SENDER.lock().unwrap().replace(tx);
 
+    // 初始化MinHook
+    unsafe {
+        minhook_sys::MH_Initialize();
+    }
     if let Err(e) = damage::install_hook() {
         error!("初始化伤害钩子错误: {}", e);
     };
+    if let Err(e) = monster::install_hook() {
+        error!("初始化怪物钩子错误，怪物出现/消失事件将不可用: {}", e);
+    };
 
     rx
 }
 
 pub async fn event_forwarder(mut hooks_rx: Receiver<Event>, main_tx: Sender<Event>) {
     while let Some(event) = hooks_rx.recv().await {
-        if let Event::Damage { damage, .. } = event {
-            debug!("on Event::Damage damage = {}", damage);
+        if let Event::Damage {
+            damage,
+            is_crit,
+            is_tenderized,
+            attack_id,
+            ..
+        } = event
+        {
+            debug!(
+                "on Event::Damage damage = {}, crit = {}, tenderized = {}, attack_id = {}",
+                damage, is_crit, is_tenderized, attack_id
+            );
+        }
+        match event {
+            Event::MonsterSpawned {
+                monster,
+                monster_id,
+                variant,
+            } => {
+                debug!("on Event::MonsterSpawned monster = {:#x}, id = {}, variant = {}", monster, monster_id, variant)
+            }
+            Event::MonsterDespawned { monster, monster_id } => {
+                debug!("on Event::MonsterDespawned monster = {:#x}, id = {}", monster, monster_id)
+            }
+            _ => {}
         }
         if let Err(e) = main_tx.send(event).await {
             error!("钩子消息转发失败：{}", e);
@@ -0,0 +1,81 @@
+use std::ffi::c_int;
+use std::os::raw::c_void;
+use std::ptr::{self, addr_of_mut};
+
+use log::error;
+use mhw_toolkit::game::address::{self, AddressRepository};
+
+use crate::event::Event;
+
+use super::HOOKS_SENDER;
+
+type MonsterCtorFunctionType = extern "C" fn(*mut c_void, c_int, c_int) -> *mut c_void;
+type MonsterDtorFunctionType = extern "C" fn(*mut c_void);
+static mut ORIGINAL_CTOR_FUNCTION: *mut c_void = ptr::null_mut();
+static mut ORIGINAL_DTOR_FUNCTION: *mut c_void = ptr::null_mut();
+
+extern "C" fn hook_ctor(monster: *mut c_void, monster_id: c_int, variant: c_int) -> *mut c_void {
+    // 先调用原始函数完成初始化
+    let result = unsafe {
+        let original: MonsterCtorFunctionType = std::mem::transmute(ORIGINAL_CTOR_FUNCTION);
+        original(monster, monster_id, variant)
+    };
+    send_event(Event::MonsterSpawned {
+        monster: monster as usize,
+        monster_id,
+        variant,
+    });
+    result
+}
+
+extern "C" fn hook_dtor(monster: *mut c_void) {
+    // 怪物ID由 DamageCollector 根据地址补全
+    send_event(Event::MonsterDespawned {
+        monster: monster as usize,
+        monster_id: -1,
+    });
+    // 调用原始函数
+    unsafe {
+        let original: MonsterDtorFunctionType = std::mem::transmute(ORIGINAL_DTOR_FUNCTION);
+        original(monster);
+    }
+}
+
+fn send_event(event: Event) {
+    if let Some(sender) = HOOKS_SENDER.lock().unwrap().as_ref() {
+        if let Err(e) = sender.blocking_send(event) {
+            error!("发送怪物事件错误：{}", e);
+        };
+    }
+}
+
+pub fn install_hook() -> Result<(), String> {
+    // 获取目标函数地址
+    let ctor_addr = AddressRepository::get_instance().lock().unwrap().get_address(address::monster::Ctor);
+    let dtor_addr = AddressRepository::get_instance().lock().unwrap().get_address(address::monster::Dtor);
+    let (ctor_addr, dtor_addr) = match (ctor_addr, dtor_addr) {
+        (Ok(ctor_addr), Ok(dtor_addr)) => (ctor_addr, dtor_addr),
+        (Err(e), _) | (_, Err(e)) => return Err(format!("获取怪物函数地址失败：{}", e)),
+    };
+    unsafe {
+        create_hook(ctor_addr, hook_ctor as *mut c_void, addr_of_mut!(ORIGINAL_CTOR_FUNCTION))
+            .map_err(|code| format!("创建怪物创建Hook失败：code: {}", code))?;
+        create_hook(dtor_addr, hook_dtor as *mut c_void, addr_of_mut!(ORIGINAL_DTOR_FUNCTION))
+            .map_err(|code| format!("创建怪物销毁Hook失败：code: {}", code))?;
+
+        minhook_sys::MH_ApplyQueued();
+    }
+
+    Ok(())
+}
+
+unsafe fn create_hook(address: usize, detour: *mut c_void, original: *mut *mut c_void) -> Result<(), i32> {
+    let target_function = address as *mut c_void;
+    let create_hook_status = minhook_sys::MH_CreateHook(target_function, detour, original);
+    if create_hook_status == minhook_sys::MH_OK {
+        minhook_sys::MH_EnableHook(target_function);
+        Ok(())
+    } else {
+        Err(create_hook_status)
+    }
+}
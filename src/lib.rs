@@ -6,16 +6,20 @@ use tokio::sync::mpsc;
 use winapi::shared::minwindef::{BOOL, DWORD, HINSTANCE, LPVOID, TRUE};
 use winapi::um::winnt::{DLL_PROCESS_ATTACH, DLL_PROCESS_DETACH};
 
+use crate::chat_queue::ChatQueue;
 use crate::event::Event;
 use crate::triggers::TriggerManager;
 
 mod actions;
+mod chat_queue;
 mod conditions;
 mod configs;
+mod damage_ledger;
 mod event;
 mod game;
 mod game_context;
 mod handlers;
+mod personal_best;
 mod triggers;
 
 #[cfg(feature = "use_audio")]
@@ -71,6 +75,8 @@ fn main_entry() -> Result<(), String> {
         let (tx, rx) = mpsc::channel(1024);
         // 事件处理器
         tokio::spawn(async move { handlers::event_handler(rx).await });
+        // 聊天消息发送队列
+        tokio::spawn(async move { ChatQueue::instance().run().await });
         // 事件监听器
         let tx1 = tx.clone();
         tokio::spawn(async move { handlers::event_listener(tx1).await });
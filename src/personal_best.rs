@@ -0,0 +1,139 @@
+use std::{collections::HashMap, fs, path::Path, sync::Arc};
+
+use log::{error, info};
+use once_cell::sync::Lazy;
+use tokio::sync::Mutex;
+
+const PERSONAL_BEST_PATH: &str = "./nativePC/plugins/mas-personal-best.json";
+static PERSONAL_BEST: Lazy<Arc<PersonalBest>> = Lazy::new(|| Arc::new(PersonalBest::new(PERSONAL_BEST_PATH)));
+
+/// 个人最佳记录
+///
+/// 按动作记录最高伤害，保存在本地 JSON 文件中
+pub struct PersonalBest {
+    path: String,
+    records: Mutex<Option<HashMap<String, i32>>>,
+}
+
+/// 个人最佳记录更新结果
+#[derive(Clone, Debug, PartialEq)]
+pub struct PersonalBestResult {
+    /// 当前最佳记录
+    pub pb: i32,
+    /// 更新前的最佳记录
+    pub prev_pb: i32,
+    /// 是否刷新了最佳记录
+    pub is_pb: bool,
+}
+
+impl PersonalBest {
+    fn new(path: &str) -> Self {
+        PersonalBest {
+            path: path.to_string(),
+            records: Mutex::new(None),
+        }
+    }
+
+    /// 获取个人最佳记录实例
+    pub fn instance() -> Arc<PersonalBest> {
+        PERSONAL_BEST.clone()
+    }
+
+    /// 与最佳记录比较，不修改记录
+    pub async fn compare(&self, key: &str, value: i32) -> PersonalBestResult {
+        let mut records = self.records.lock().await;
+        let records = records.get_or_insert_with(|| load_records(&self.path));
+        compare_record(records, key, value)
+    }
+
+    /// 提交一次伤害，刷新最佳记录时写入文件
+    pub async fn commit(&self, key: &str, value: i32) {
+        let content = {
+            let mut records = self.records.lock().await;
+            let records = records.get_or_insert_with(|| load_records(&self.path));
+            let result = compare_record(records, key, value);
+            if !result.is_pb {
+                return;
+            }
+            info!("{} 刷新个人最佳记录：{} -> {}", key, result.prev_pb, result.pb);
+            records.insert(key.to_string(), value);
+            serde_json::to_string_pretty(records)
+        };
+        // 释放锁后再写入文件
+        let result = match content {
+            Ok(content) => tokio::fs::write(&self.path, content).await.map_err(|e| e.to_string()),
+            Err(e) => Err(e.to_string()),
+        };
+        if let Err(e) = result {
+            error!("保存个人最佳记录失败：{}", e);
+        }
+    }
+}
+
+fn compare_record(records: &HashMap<String, i32>, key: &str, value: i32) -> PersonalBestResult {
+    let prev_pb = records.get(key).copied().unwrap_or(0);
+    if value > prev_pb {
+        PersonalBestResult {
+            pb: value,
+            prev_pb,
+            is_pb: true,
+        }
+    } else {
+        PersonalBestResult {
+            pb: prev_pb,
+            prev_pb,
+            is_pb: false,
+        }
+    }
+}
+
+fn load_records(path: &str) -> HashMap<String, i32> {
+    if !Path::new(path).exists() {
+        return HashMap::new();
+    }
+    match fs::read_to_string(path)
+        .map_err(|e| e.to_string())
+        .and_then(|s| serde_json::from_str(&s).map_err(|e| e.to_string()))
+    {
+        Ok(records) => records,
+        Err(e) => {
+            error!("读取个人最佳记录失败：{}", e);
+            HashMap::new()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compare_record() {
+        let mut records = HashMap::new();
+        assert_eq!(
+            compare_record(&records, "登龙", 2100),
+            PersonalBestResult {
+                pb: 2100,
+                prev_pb: 0,
+                is_pb: true,
+            }
+        );
+        records.insert("登龙".to_string(), 2100);
+        assert_eq!(
+            compare_record(&records, "登龙", 2000),
+            PersonalBestResult {
+                pb: 2100,
+                prev_pb: 2100,
+                is_pb: false,
+            }
+        );
+        assert_eq!(
+            compare_record(&records, "登龙", 2345),
+            PersonalBestResult {
+                pb: 2345,
+                prev_pb: 2100,
+                is_pb: true,
+            }
+        );
+    }
+}
@@ -16,9 +16,30 @@ use tokio::sync::{Mutex, RwLock};
 use crate::{
     actions::{self, ActionContext, AsAction},
     conditions::{
-        charge_blade::ChargeBladeCondition, damage::DamageCondition, fsm::FsmCondition,
-        insect_glaive::InsectGlaiveCondition, longsword::LongswordCondition, quest_state::QuestStateCondition,
-        use_item::UseItemCondition, weapon_id::WeaponTypeCondition,
+        charge_blade::ChargeBladeCondition,
+        charge_level::ChargeLevelCondition,
+        count::CountCondition,
+        damage::{DamageCondition, DamageTriggerCondition},
+        dual_blades::DualBladesCondition,
+        faint::FaintCondition,
+        fsm::FsmCondition,
+        gunlance::GunlanceCondition,
+        hunting_horn::HuntingHornCondition,
+        insect_glaive::InsectGlaiveCondition,
+        longsword::LongswordCondition,
+        monster::MonsterCondition,
+        monster_state::MonsterStateCondition,
+        personal_best::PersonalBestCondition,
+        player_vitals::PlayerVitalsCondition,
+        quest_state::QuestStateCondition,
+        quest_summary::QuestSummaryCondition,
+        ranged::RangedWeaponCondition,
+        sharpness::SharpnessCondition,
+        specialized_tool::SpecializedToolCondition,
+        status_effect::StatusEffectCondition,
+        switch_axe::SwitchAxeCondition,
+        use_item::UseItemCondition,
+        weapon_id::WeaponTypeCondition,
     },
     configs::{self, ActionMode, TriggerCondition},
     event::{Event, EventType},
@@ -27,21 +48,33 @@ use crate::{
 
 pub type SharedContext = Arc<RwLock<Context>>;
 
+/// 触发时间（毫秒时间戳），取事件分发时刻，在触发条件满足时写入 `ActionContext`
+pub const TRIGGER_TIME_KEY: &str = "trigger_time";
+
 #[async_trait]
 pub trait AsTriggerCondition: Send + Sync {
     async fn check(&self, event: &Event, action_ctx: &ActionContext) -> bool;
     fn event_type(&self) -> EventType;
+    /// 所有条件满足且触发器实际触发后调用
+    async fn on_fired(&self) {}
 }
 
 #[async_trait]
 pub trait AsCheckCondition: Send + Sync {
     async fn check(&self, action_ctx: &ActionContext) -> bool;
+    /// 触发条件满足时调用，先于所有检查条件
+    async fn on_triggered(&self) {}
+    /// 所有条件满足且触发器实际触发后调用
+    async fn on_fired(&self) {}
+    /// 进入或离开据点时调用，用于重置内部状态
+    async fn reset(&self) {}
 }
 
 #[async_trait]
 pub trait AsTrigger: Send + Sync {
     fn event_type(&self) -> EventType;
-    async fn on_event(&mut self, event: &Event);
+    /// `trigger_time` 为事件分发时的时间戳（毫秒）
+    async fn on_event(&mut self, event: &Event, trigger_time: i64);
     async fn on_event_reset(&mut self);
 }
 
@@ -52,6 +85,8 @@ pub struct TriggerBuilder {
     check_conditions: Vec<Box<dyn AsCheckCondition>>,
     action_mode: ActionMode,
     cooldown: Option<SingleCoolDown>,
+    cooldown_group: Option<Arc<SingleCoolDown>>,
+    fire_limit: Option<FireLimit>,
     event_type: EventType,
     action_idx: AtomicI32,
 }
@@ -66,9 +101,9 @@ impl TriggerFns {
         Self { builder }
     }
 
-    pub async fn execute(&mut self, event: &Event) {
+    pub async fn execute(&mut self, event: &Event, trigger_time: i64) {
         let action_ctx = Arc::new(Mutex::new(HashMap::new()));
-        if !self.builder.check_conditions(event, &action_ctx).await {
+        if !self.builder.check_conditions(event, trigger_time, &action_ctx).await {
             return;
         }
         match self.builder.action_mode {
@@ -93,6 +128,13 @@ impl TriggerFns {
     }
 
     pub async fn reset(&mut self) {
+        if let Some(fire_limit) = &self.builder.fire_limit {
+            fire_limit.reset_quest();
+        }
+        // 与冷却组一致，返回据点时重置自身冷却
+        if let Some(cooldown) = &self.builder.cooldown {
+            cooldown.reset().await;
+        }
         if let ActionMode::SequentialAll = self.builder.action_mode {
             stream::iter(self.builder.actions.iter())
                 .for_each(|e| async move {
@@ -113,6 +155,8 @@ impl TriggerBuilder {
             check_conditions: Vec::new(),
             action_mode: ActionMode::SequentialAll,
             cooldown: None,
+            cooldown_group: None,
+            fire_limit: None,
             event_type,
             action_idx: AtomicI32::new(0),
         }
@@ -126,6 +170,14 @@ impl TriggerBuilder {
         self.cooldown = Some(cooldown);
     }
 
+    pub fn set_cooldown_group(&mut self, cooldown_group: Arc<SingleCoolDown>) {
+        self.cooldown_group = Some(cooldown_group);
+    }
+
+    pub fn set_fire_limit(&mut self, fire_limit: FireLimit) {
+        self.fire_limit = Some(fire_limit);
+    }
+
     pub fn set_action_mode(&mut self, action_mode: ActionMode) {
         self.action_mode = action_mode;
     }
@@ -150,12 +202,12 @@ impl TriggerBuilder {
         }
     }
 
-    async fn check_conditions(&self, event: &Event, action_ctx: &ActionContext) -> bool {
+    async fn check_conditions(&self, event: &Event, trigger_time: i64, action_ctx: &ActionContext) -> bool {
         // 状态重置条件判断
-        if let ActionMode::SequentialOne = self.action_mode {
-            if let Event::QuestStateChanged { new, old, .. } = event {
-                // 进入据点或离开据点时
-                if *new == 1 || *old == 1 {
+        if let Event::QuestStateChanged { new, old, .. } = event {
+            // 进入据点或离开据点时
+            if *new == 1 || *old == 1 {
+                if let ActionMode::SequentialOne = self.action_mode {
                     // reset idx
                     self.action_idx.store(0, Ordering::SeqCst);
                     // reset cooldown
@@ -163,12 +215,17 @@ impl TriggerBuilder {
                         cooldown.reset().await;
                     }
                 }
+                // reset check conditions
+                stream::iter(self.check_conditions.iter()).for_each(|c| async move { c.reset().await }).await;
             }
         }
         // 判断触发器
         if !self.trigger_condition.check(event, action_ctx).await {
             return false;
         }
+        action_ctx.lock().await.insert(TRIGGER_TIME_KEY.to_string(), trigger_time.to_string());
+        // 通知检查器触发条件已满足
+        stream::iter(self.check_conditions.iter()).for_each(|c| async move { c.on_triggered().await }).await;
         // 判断检查器
         let checked = stream::iter(self.check_conditions.iter())
             .all(|c| {
@@ -179,12 +236,35 @@ impl TriggerBuilder {
         if !checked {
             return false;
         }
+        // 判断触发次数限制
+        if let Some(fire_limit) = &self.fire_limit {
+            if fire_limit.is_reached() {
+                return false;
+            }
+        }
         // 判断冷却
+        // 冷却组由多个触发器共享，需确认自身冷却结束后再占用
+        if let Some(cd) = &self.cooldown {
+            if !cd.is_ready().await {
+                return false;
+            }
+        };
+        if let Some(group_cd) = &self.cooldown_group {
+            if !group_cd.check_set().await {
+                return false;
+            }
+        };
         if let Some(cd) = &self.cooldown {
             if !cd.check_set().await {
                 return false;
             }
         };
+        if let Some(fire_limit) = &self.fire_limit {
+            fire_limit.record();
+        }
+        // 通知触发条件和检查器触发器已触发
+        self.trigger_condition.on_fired().await;
+        stream::iter(self.check_conditions.iter()).for_each(|c| async move { c.on_fired().await }).await;
         true
     }
 
@@ -221,11 +301,11 @@ impl AsTrigger for Trigger {
         self.event_type.clone()
     }
 
-    async fn on_event(&mut self, event: &Event) {
-        self.trigger_fns.execute(event).await
+    async fn on_event(&mut self, event: &Event, trigger_time: i64) {
+        self.trigger_fns.execute(event, trigger_time).await
     }
 
-    /// 重置触发器触发次数
+    /// 重置触发器触发次数和冷却
     async fn on_event_reset(&mut self) {
         self.trigger_fns.reset().await
     }
@@ -237,6 +317,7 @@ pub struct TriggerManager {
     triggers: HashMap<EventType, Vec<Arc<Mutex<Trigger>>>>,
     all_triggers: Vec<Arc<Mutex<Trigger>>>,
     shared_ctx: Arc<RwLock<Context>>,
+    cd_groups: Arc<CoolDownGroups>,
 }
 
 impl std::fmt::Debug for TriggerManager {
@@ -246,11 +327,12 @@ impl std::fmt::Debug for TriggerManager {
 }
 
 impl TriggerManager {
-    pub fn new(shared_ctx: SharedContext) -> Self {
+    pub fn new(shared_ctx: SharedContext, cd_groups: CoolDownGroups) -> Self {
         TriggerManager {
             triggers: HashMap::new(),
             all_triggers: Vec::new(),
             shared_ctx,
+            cd_groups: Arc::new(cd_groups),
         }
     }
 
@@ -263,17 +345,21 @@ impl TriggerManager {
         self.all_triggers.push(shared_trigger);
     }
 
-    pub async fn broadcast(&self, event: &Event) {
+    pub async fn broadcast(&self, event: &Event, trigger_time: i64) {
         stream::iter(self.all_triggers.iter())
-            .for_each_concurrent(None, |trigger| async move { trigger.lock().await.on_event(event).await })
+            .for_each_concurrent(
+                None,
+                |trigger| async move { trigger.lock().await.on_event(event, trigger_time).await },
+            )
             .await;
     }
 
-    pub async fn broadcast_and_reset(&self, event: &Event) {
+    pub async fn broadcast_and_reset(&self, event: &Event, trigger_time: i64) {
+        self.cd_groups.reset().await;
         stream::iter(self.all_triggers.iter())
             .for_each_concurrent(None, |trigger| async move {
                 let mut locked = trigger.lock().await;
-                locked.on_event(event).await;
+                locked.on_event(event, trigger_time).await;
                 locked.on_event_reset().await;
             })
             .await;
@@ -285,12 +371,14 @@ impl TriggerManager {
     }
 
     pub async fn dispatch(&mut self, event: &Event) {
+        // 在分发时记录触发时间，避免受到触发器锁等待的影响
+        let trigger_time = Utc::now().timestamp_millis();
         // 需要广播的消息
         if let Event::QuestStateChanged { new, .. } = event {
             if new == &1 {
-                self.broadcast_and_reset(event).await;
+                self.broadcast_and_reset(event, trigger_time).await;
             } else {
-                self.broadcast(event).await;
+                self.broadcast(event, trigger_time).await;
             }
             return;
         }
@@ -301,7 +389,7 @@ impl TriggerManager {
                 let event_clone = event.clone();
                 tokio::task::spawn(async move {
                     let mut locked = trigger_clone.lock().await;
-                    locked.on_event(&event_clone).await;
+                    locked.on_event(&event_clone, trigger_time).await;
                 });
             }
         }
@@ -309,7 +397,7 @@ impl TriggerManager {
 }
 
 /// 通过配置注册 Trigger
-pub fn register_trigger(t_cfg: &configs::Trigger, shared_ctx: SharedContext) -> Trigger {
+pub fn register_trigger(t_cfg: &configs::Trigger, shared_ctx: SharedContext, cd_groups: &CoolDownGroups) -> Trigger {
     let t_cfg = t_cfg.clone();
     let action_mode = t_cfg.action_mode.unwrap_or(configs::ActionMode::SequentialAll);
     let t_cond = register_trigger_condition(&t_cfg.trigger_on, shared_ctx.clone());
@@ -320,6 +408,12 @@ pub fn register_trigger(t_cfg: &configs::Trigger, shared_ctx: SharedContext) ->
         builder.set_name(name);
     }
     builder.set_cooldown(SingleCoolDown::new(t_cfg.cooldown.unwrap_or(0.0)));
+    if let Some(group_cd) = t_cfg.cooldown_group.as_ref().and_then(|name| cd_groups.get(name)) {
+        builder.set_cooldown_group(group_cd);
+    }
+    if t_cfg.max_fires_per_quest.is_some() || t_cfg.max_fires_per_session.is_some() {
+        builder.set_fire_limit(FireLimit::new(t_cfg.max_fires_per_quest, t_cfg.max_fires_per_session));
+    }
     t_cfg
         .check
         .iter()
@@ -354,13 +448,41 @@ fn register_check_condition(
     shared_ctx: SharedContext,
 ) -> Box<dyn AsCheckCondition> {
     match check_cond {
-        configs::CheckCondition::LongswordLevel { .. } => {
+        configs::CheckCondition::LongswordLevel { .. } | configs::CheckCondition::LongswordSpirit { .. } => {
             Box::new(LongswordCondition::new_check(check_cond, shared_ctx))
         }
         configs::CheckCondition::WeaponType { .. } => Box::new(WeaponTypeCondition::new_check(check_cond, shared_ctx)),
         configs::CheckCondition::QuestState { .. } => Box::new(QuestStateCondition::new_check(check_cond, shared_ctx)),
         configs::CheckCondition::Fsm { .. } => Box::new(FsmCondition::new_check(check_cond, shared_ctx)),
         configs::CheckCondition::Damage { .. } => Box::new(DamageCondition::new_check(check_cond, shared_ctx)),
+        configs::CheckCondition::Count { .. } => Box::new(CountCondition::new_check(check_cond)),
+        configs::CheckCondition::PersonalBest { .. } => Box::new(PersonalBestCondition::new_check(check_cond)),
+        configs::CheckCondition::Faints { .. } => Box::new(FaintCondition::new_check(check_cond, shared_ctx)),
+        configs::CheckCondition::PlayerVitals { .. } => {
+            Box::new(PlayerVitalsCondition::new_check(check_cond, shared_ctx))
+        }
+        configs::CheckCondition::MonsterState { .. } => {
+            Box::new(MonsterStateCondition::new_check(check_cond, shared_ctx))
+        }
+        configs::CheckCondition::SpecializedTool { .. } => {
+            Box::new(SpecializedToolCondition::new_check(check_cond, shared_ctx))
+        }
+        configs::CheckCondition::StatusEffect { .. } => {
+            Box::new(StatusEffectCondition::new_check(check_cond, shared_ctx))
+        }
+        configs::CheckCondition::Sharpness { .. } => Box::new(SharpnessCondition::new_check(check_cond, shared_ctx)),
+        configs::CheckCondition::SwitchAxe { .. } => Box::new(SwitchAxeCondition::new_check(check_cond, shared_ctx)),
+        configs::CheckCondition::Gunlance { .. } => Box::new(GunlanceCondition::new_check(check_cond, shared_ctx)),
+        configs::CheckCondition::HuntingHorn { .. } => {
+            Box::new(HuntingHornCondition::new_check(check_cond, shared_ctx))
+        }
+        configs::CheckCondition::RangedWeapon { .. } => {
+            Box::new(RangedWeaponCondition::new_check(check_cond, shared_ctx))
+        }
+        configs::CheckCondition::DualBlades { .. } => Box::new(DualBladesCondition::new_check(check_cond, shared_ctx)),
+        configs::CheckCondition::ChargeLevel { .. } => {
+            Box::new(ChargeLevelCondition::new_check(check_cond, shared_ctx))
+        }
     }
 }
 
@@ -369,7 +491,7 @@ fn register_trigger_condition(
     shared_ctx: SharedContext,
 ) -> Box<dyn AsTriggerCondition> {
     match trigger_cond {
-        TriggerCondition::LongswordLevelChanged { .. } => {
+        TriggerCondition::LongswordLevelChanged { .. } | TriggerCondition::LongswordSpirit { .. } => {
             Box::new(LongswordCondition::new_trigger(trigger_cond, shared_ctx))
         }
         TriggerCondition::WeaponType { .. } => Box::new(WeaponTypeCondition::new_trigger(trigger_cond, shared_ctx)),
@@ -379,7 +501,28 @@ fn register_trigger_condition(
             Box::new(InsectGlaiveCondition::new_trigger(trigger_cond, shared_ctx))
         }
         TriggerCondition::ChargeBlade { .. } => Box::new(ChargeBladeCondition::new_trigger(trigger_cond, shared_ctx)),
+        TriggerCondition::SwitchAxe { .. } => Box::new(SwitchAxeCondition::new_trigger(trigger_cond, shared_ctx)),
+        TriggerCondition::Gunlance { .. } => Box::new(GunlanceCondition::new_trigger(trigger_cond, shared_ctx)),
+        TriggerCondition::HuntingHornSong { .. } | TriggerCondition::HuntingHornQueue { .. } => {
+            Box::new(HuntingHornCondition::new_trigger(trigger_cond, shared_ctx))
+        }
+        TriggerCondition::RangedWeapon { .. } => Box::new(RangedWeaponCondition::new_trigger(trigger_cond, shared_ctx)),
+        TriggerCondition::DualBlades { .. } => Box::new(DualBladesCondition::new_trigger(trigger_cond, shared_ctx)),
+        TriggerCondition::ChargeLevel { .. } => Box::new(ChargeLevelCondition::new_trigger(trigger_cond, shared_ctx)),
         TriggerCondition::UseItem { .. } => Box::new(UseItemCondition::new_trigger(trigger_cond)),
+        TriggerCondition::QuestSummary { .. } => Box::new(QuestSummaryCondition::new_trigger(trigger_cond)),
+        TriggerCondition::Damage { .. } => Box::new(DamageTriggerCondition::new_trigger(trigger_cond)),
+        TriggerCondition::Monster { .. } => Box::new(MonsterCondition::new_trigger(trigger_cond)),
+        TriggerCondition::MonsterState { .. } => Box::new(MonsterStateCondition::new_trigger(trigger_cond, shared_ctx)),
+        TriggerCondition::PlayerFainted { .. } => Box::new(FaintCondition::new_trigger(trigger_cond, shared_ctx)),
+        TriggerCondition::PlayerVitals { .. } => Box::new(PlayerVitalsCondition::new_trigger(trigger_cond, shared_ctx)),
+        TriggerCondition::SpecializedTool { .. } => {
+            Box::new(SpecializedToolCondition::new_trigger(trigger_cond, shared_ctx))
+        }
+        TriggerCondition::StatusEffect { .. } => Box::new(StatusEffectCondition::new_trigger(trigger_cond, shared_ctx)),
+        TriggerCondition::SharpnessChanged { .. } => {
+            Box::new(SharpnessCondition::new_trigger(trigger_cond, shared_ctx))
+        }
     }
 }
 
@@ -404,6 +547,15 @@ impl SingleCoolDown {
         *r = None;
     }
 
+    /// 冷却是否已结束（不更新冷却记录）
+    pub async fn is_ready(&self) -> bool {
+        let cd_dur = Duration::try_milliseconds((self.cooldown * 1000.0) as i64).unwrap_or_default();
+        match *self.record.lock().await {
+            Some(last_time) => last_time + cd_dur <= Utc::now(),
+            None => true,
+        }
+    }
+
     pub async fn check_set(&self) -> bool {
         let now = Utc::now();
         let cd_dur = Duration::try_milliseconds((self.cooldown * 1000.0) as i64).unwrap_or_default();
@@ -427,3 +579,71 @@ impl SingleCoolDown {
         }
     }
 }
+
+/// 冷却组管理器
+///
+/// 同名冷却组共享同一个冷却时间管理器，冷却时间取自全局配置 `cooldown_groups`，
+/// 未设置的冷却组使用全局冷却时间 `trigger_cd`。返回据点时与触发器自身冷却一同重置
+pub struct CoolDownGroups {
+    groups: HashMap<String, Arc<SingleCoolDown>>,
+}
+
+impl CoolDownGroups {
+    pub fn new(cfg: &configs::Config) -> Self {
+        let mut groups = HashMap::new();
+        for name in cfg.trigger.iter().filter_map(|t| t.cooldown_group.as_ref()) {
+            groups.entry(name.clone()).or_insert_with(|| {
+                let cooldown = cfg.cooldown_groups.get(name).copied().unwrap_or(cfg.trigger_cd);
+                Arc::new(SingleCoolDown::new(cooldown))
+            });
+        }
+        Self { groups }
+    }
+
+    pub fn get(&self, name: &str) -> Option<Arc<SingleCoolDown>> {
+        self.groups.get(name).cloned()
+    }
+
+    /// 重置所有冷却组
+    pub async fn reset(&self) {
+        stream::iter(self.groups.values()).for_each(|cd| async move { cd.reset().await }).await;
+    }
+}
+
+/// 触发次数限制
+pub struct FireLimit {
+    /// 单次任务内最多触发次数
+    max_per_quest: Option<i32>,
+    /// 本次游戏内最多触发次数
+    max_per_session: Option<i32>,
+    quest_fires: AtomicI32,
+    session_fires: AtomicI32,
+}
+
+impl FireLimit {
+    pub fn new(max_per_quest: Option<i32>, max_per_session: Option<i32>) -> Self {
+        Self {
+            max_per_quest,
+            max_per_session,
+            quest_fires: AtomicI32::new(0),
+            session_fires: AtomicI32::new(0),
+        }
+    }
+
+    /// 是否已达到触发次数上限
+    pub fn is_reached(&self) -> bool {
+        self.max_per_quest.is_some_and(|max| self.quest_fires.load(Ordering::SeqCst) >= max)
+            || self.max_per_session.is_some_and(|max| self.session_fires.load(Ordering::SeqCst) >= max)
+    }
+
+    /// 记录一次触发
+    pub fn record(&self) {
+        self.quest_fires.fetch_add(1, Ordering::SeqCst);
+        self.session_fires.fetch_add(1, Ordering::SeqCst);
+    }
+
+    /// 重置单次任务内的触发次数
+    pub fn reset_quest(&self) {
+        self.quest_fires.store(0, Ordering::SeqCst);
+    }
+}